@@ -0,0 +1,93 @@
+use display_interface::{WriteOnlyDataCommand, DisplayError};
+use rp2040_hal::pac;
+use picorender::transfer::DisplayDma;
+use picorender::damage::DamageRect;
+use picorender::st7789::St7789;
+
+// DMA channel feeding the display lines to SPI0, it raises DMA_IRQ_0 after every line
+pub const CHANNEL: usize = 0;
+
+// data request line of SPI0's TX FIFO, the DMA only writes when there's room in it
+const DREQ_SPI0_TX: u8 = 16;
+
+// called at the start of the DMA interrupt
+pub fn clear_interrupt()
+{
+	let dma = unsafe { &*pac::DMA::ptr() };
+	dma.ints0.write(|w| unsafe { w.ints0().bits(1 << CHANNEL) });
+}
+
+// Sends pixel data with the DMA while the commands in between frames go through the
+// regular blocking display interface, which shares SPI0 with the DMA.
+pub struct SpiDma<DI: WriteOnlyDataCommand>
+{
+	pub display: St7789<DI>
+}
+
+impl<DI: WriteOnlyDataCommand> SpiDma<DI>
+{
+	// the DMA block has to be out of reset already
+	pub fn new(display: St7789<DI>) -> SpiDma<DI>
+	{
+		let spi = unsafe { &*pac::SPI0::ptr() };
+		let dma = unsafe { &*pac::DMA::ptr() };
+
+		// let the TX FIFO request data from the DMA and the channel interrupt on completion
+		spi.sspdmacr.modify(|_, w| w.txdmae().set_bit());
+		dma.inte0.modify(|r, w| unsafe { w.inte0().bits(r.inte0().bits() | (1 << CHANNEL)) });
+
+		SpiDma{
+			display
+		}
+	}
+
+	// Waits for the last byte to leave the SPI and drops what the DMA made it receive,
+	// the blocking writes of the display interface count on an empty RX FIFO.
+	fn wait_idle(&self)
+	{
+		let spi = unsafe { &*pac::SPI0::ptr() };
+
+		while spi.sspsr.read().bsy().bit_is_set()
+		{
+		}
+
+		while spi.sspsr.read().rne().bit_is_set()
+		{
+			let _ = spi.sspdr.read();
+		}
+	}
+}
+
+impl<DI: WriteOnlyDataCommand> DisplayDma for SpiDma<DI>
+{
+	type Error = DisplayError;
+
+	// called from the DMA interrupt, an error goes back through FrameTransfer::line_done
+	fn begin_window(&mut self, rect: &DamageRect) -> Result<(), DisplayError>
+	{
+		self.wait_idle();
+
+		// leaves the data/command pin on data for the DMA
+		self.display.start_ram_write(rect.left as u16, rect.top as u16, rect.right as u16, rect.bottom as u16)
+	}
+
+	fn start_line(&mut self, data: &[u8])
+	{
+		let spi = unsafe { &*pac::SPI0::ptr() };
+		let channel = unsafe { &(*pac::DMA::ptr()).ch[CHANNEL] };
+
+		channel.ch_read_addr.write(|w| unsafe { w.bits(data.as_ptr() as u32) });
+		channel.ch_write_addr.write(|w| unsafe { w.bits(spi.sspdr.as_ptr() as u32) });
+		channel.ch_trans_count.write(|w| unsafe { w.bits(data.len() as u32) });
+
+		// bytes from memory to the same FIFO address, paced by the SPI, chaining to itself means no chaining
+		channel.ch_ctrl_trig.write(|w| unsafe {
+			w.data_size().size_byte()
+				.incr_read().set_bit()
+				.incr_write().clear_bit()
+				.treq_sel().bits(DREQ_SPI0_TX)
+				.chain_to().bits(CHANNEL as u8)
+				.en().set_bit()
+		});
+	}
+}
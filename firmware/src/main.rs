@@ -0,0 +1,340 @@
+#![no_std]
+#![no_main]
+
+mod dma;
+mod multicore;
+
+use core::cell::RefCell;
+use cortex_m::interrupt::Mutex;
+use cortex_m_rt::entry;
+use panic_halt as _;
+use rp2040_hal as hal;
+use hal::clocks::Clock;
+use hal::pac;
+use hal::pac::interrupt;
+use embedded_hal::digital::v2::{OutputPin,InputPin};
+use embedded_time::rate::*;
+use embedded_time::fixed_point::FixedPoint;
+use display_interface_spi::SPIInterfaceNoCS;
+use picorender::{SCREEN_WIDTH, SCREEN_HEIGHT, depthbuffer};
+use picorender::st7789::St7789;
+use picorender::viewport::Viewport;
+use picorender::framebuffer::Surface;
+use picorender::scene::{Input, Scene};
+use picorender::transfer::FrameTransfer;
+#[cfg(not(feature = "tiled"))]
+use picorender::damage::{DamageList, DamageRect};
+use picorender::pipeline::{FrameCells, Renderer, Presenter, SharedInput};
+#[cfg(feature = "tiled")]
+use picorender::tiles::TriangleBins;
+
+#[link_section = ".boot2"]
+#[used]
+pub static BOOT2: [u8; 256] = rp2040_boot2::BOOT_LOADER_W25Q080;
+
+const XTAL_FREQ_HZ: u32 = 12_000_000u32;
+
+// Core1 renders frames, core0 sends them to the display and reads the buttons. The frames go
+// back and forth through the SIO FIFOs, see picorender::pipeline.
+const FRAME_COUNT: usize = 2;
+
+// Two framebuffers and a full screen depth buffer don't fit into the RP2040's RAM, so frames
+// are rendered in bands that share a depth buffer covering only this many rows.
+#[cfg(not(feature = "tiled"))]
+const BAND_HEIGHT: usize = SCREEN_HEIGHT / 2;
+
+#[cfg(not(feature = "tiled"))]
+struct Frame
+{
+	pixels: [u8; SCREEN_WIDTH * SCREEN_HEIGHT],
+	// what was drawn into the frame, erased before the next one is rendered into it
+	drawn: DamageList,
+	// what differs from the frame before, only that is sent
+	changed: DamageList
+}
+
+#[cfg(not(feature = "tiled"))]
+impl Frame
+{
+	const fn new() -> Frame
+	{
+		Frame{
+			pixels: [0u8; SCREEN_WIDTH * SCREEN_HEIGHT],
+			drawn: DamageList::new(),
+			changed: DamageList::new()
+		}
+	}
+}
+
+// With the tiled renderer the frames going between the cores are strips of the screen, the
+// triangles are binned once per frame and every strip is drawn from the bins. That takes two
+// strips and a strip sized depth buffer instead of two whole frames.
+#[cfg(feature = "tiled")]
+const STRIP_HEIGHT: usize = 32;
+
+// triangles binned per frame, the textured cube has 12
+#[cfg(feature = "tiled")]
+const BIN_CAPACITY: usize = 64;
+
+#[cfg(feature = "tiled")]
+struct Frame
+{
+	pixels: [u8; SCREEN_WIDTH * STRIP_HEIGHT],
+	// screen row of the first line of the strip, and how many lines of pixels are used
+	first_row: usize,
+	rows: usize
+}
+
+#[cfg(feature = "tiled")]
+impl Frame
+{
+	const fn new() -> Frame
+	{
+		Frame{
+			pixels: [0u8; SCREEN_WIDTH * STRIP_HEIGHT],
+			first_row: 0,
+			rows: 0
+		}
+	}
+}
+
+static FRAMES: FrameCells<Frame, FRAME_COUNT> = FrameCells::new([Frame::new(), Frame::new()]);
+static INPUT: SharedInput = SharedInput::new();
+
+type DisplayInterface = SPIInterfaceNoCS<hal::Spi<hal::spi::Enabled, pac::SPI0, 8>, hal::gpio::Pin<hal::gpio::bank0::Gpio16, hal::gpio::PushPullOutput>>;
+
+// shared between the main loop and the DMA interrupt, which moves the transfer on to the next line
+static TRANSFER: Mutex<RefCell<Option<FrameTransfer<dma::SpiDma<DisplayInterface>>>>> = Mutex::new(RefCell::new(None));
+
+#[entry]
+fn main() -> ! 
+{
+	// init peripherals
+	let mut pac = pac::Peripherals::take().unwrap();
+    let mut watchdog = hal::Watchdog::new(pac.WATCHDOG);
+	let core = pac::CorePeripherals::take().unwrap();
+	
+	// init clocks
+    let clocks = hal::clocks::init_clocks_and_plls(
+        XTAL_FREQ_HZ,
+        pac.XOSC,
+        pac.CLOCKS,
+        pac.PLL_SYS,
+        pac.PLL_USB,
+        &mut pac.RESETS,
+        &mut watchdog,
+    )
+    .ok()
+    .unwrap();
+
+	// the DMA block starts out held in reset
+	pac.RESETS.reset.modify(|_, w| w.dma().clear_bit());
+	while pac.RESETS.reset_done.read().dma().bit_is_clear()
+	{
+	}
+
+	let mut delay = cortex_m::delay::Delay::new(core.SYST, clocks.system_clock.freq().integer());
+
+    let mut sio = hal::Sio::new(pac.SIO);
+
+	// rendering starts right away, the first frames wait in the FIFO until the display is ready
+	multicore::spawn_core1(&mut pac.PSM, &mut pac.PPB, &mut sio, render_core);
+	let mut fifo = multicore::CoreFifo{fifo: sio.fifo};
+
+    let pins = hal::gpio::Pins::new(
+        pac.IO_BANK0,
+        pac.PADS_BANK0,
+        sio.gpio_bank0,
+        &mut pac.RESETS,
+    );
+
+	let _spi_sclk = pins.gpio18.into_mode::<hal::gpio::FunctionSpi>(); // sclk
+    let _spi_mosi = pins.gpio19.into_mode::<hal::gpio::FunctionSpi>(); // mosi
+	let _spi_data_command = pins.gpio16.into_push_pull_output();
+	let _spi_chip_select = pins.gpio17.into_push_pull_output();
+	let spi = hal::Spi::<_, _, 8>::new(pac.SPI0).init(&mut pac.RESETS, clocks.peripheral_clock.freq(), 62_500_000u32.Hz(), &embedded_hal::spi::MODE_2);
+
+	// init output pins
+	let mut led_pin = pins.gpio25.into_push_pull_output();
+	let mut r_pin = pins.gpio6.into_push_pull_output();
+	let mut g_pin = pins.gpio7.into_push_pull_output();
+	let mut b_pin = pins.gpio8.into_push_pull_output();
+	let mut reset_pin = pins.gpio20.into_push_pull_output();
+
+	// init input pins
+	let input_a = pins.gpio12.into_pull_up_input();
+	let input_b = pins.gpio13.into_pull_up_input();
+	let input_x = pins.gpio14.into_pull_up_input();
+	let input_y = pins.gpio15.into_pull_up_input();
+
+	// turn off LEDs (set_high actually turns them off)
+	r_pin.set_high().unwrap();
+	g_pin.set_high().unwrap();
+	b_pin.set_high().unwrap();
+
+	// display init
+	let mut display = St7789::new(SPIInterfaceNoCS::new(spi, _spi_data_command));
+	display.init(&mut delay, &mut reset_pin).unwrap();
+
+	// from here on the display is fed by the DMA
+	cortex_m::interrupt::free(|cs| TRANSFER.borrow(cs).replace(Some(FrameTransfer::new(dma::SpiDma::new(display)))));
+	unsafe
+	{
+		pac::NVIC::unmask(pac::Interrupt::DMA_IRQ_0);
+	}
+
+	let mut presenter = Presenter::<FRAME_COUNT>::new();
+
+	#[allow(clippy::empty_loop)]
+	loop
+	{
+		// take user input, the buttons pull their pins low
+		INPUT.store(&Input{
+			a: input_a.is_low().unwrap(),
+			b: input_b.is_low().unwrap(),
+			x: input_x.is_low().unwrap(),
+			y: input_y.is_low().unwrap()
+		});
+
+		// once the frame is on the display core1 can render into it again
+		if presenter.sending().is_some() && cortex_m::interrupt::free(|cs| TRANSFER.borrow(cs).borrow_mut().as_mut().unwrap().take_finished()).is_some()
+		{
+			presenter.frame_sent(&mut fifo);
+			led_pin.set_high().unwrap();
+		}
+
+		// draw the next finished frame to the screen
+		if let Some(index) = presenter.next_frame(&mut fifo)
+		{
+			// core0 has the frame until frame_sent, the transfer gives the pixels back before that
+			let frame: &'static mut Frame = unsafe { FRAMES.get(index) };
+			#[cfg(not(feature = "tiled"))]
+			cortex_m::interrupt::free(|cs| TRANSFER.borrow(cs).borrow_mut().as_mut().unwrap().start_regions(&mut frame.pixels, &frame.changed)).unwrap();
+			#[cfg(feature = "tiled")]
+			cortex_m::interrupt::free(|cs| TRANSFER.borrow(cs).borrow_mut().as_mut().unwrap().start_strip(&mut frame.pixels[..frame.rows * SCREEN_WIDTH], frame.first_row)).unwrap();
+		}
+	}
+}
+
+// core1: renders into the frames it gets back from core0
+#[cfg(not(feature = "tiled"))]
+fn render_core() -> !
+{
+	let mut fifo = multicore::core1_fifo();
+	let mut renderer = Renderer::<FRAME_COUNT>::new();
+
+	// per pixel depth, smaller is closer
+	let depthbuffer: &'static mut [u16] = cortex_m::singleton!(: [u16; SCREEN_WIDTH * BAND_HEIGHT] = [depthbuffer::DEPTH_FAR; SCREEN_WIDTH * BAND_HEIGHT]).unwrap();
+
+	// the 3D scene covers the whole screen
+	let mut scene = Scene::new(Viewport::fullscreen());
+
+	// what the frame on the display drew, the display starts out with whatever was in its memory
+	// so the first frame is sent whole
+	let mut shown_drawn = DamageList::new();
+	shown_drawn.add(DamageRect{left: 0, top: 0, right: SCREEN_WIDTH - 1, bottom: SCREEN_HEIGHT - 1});
+
+	loop
+	{
+		let index = loop
+		{
+			if let Some(index) = renderer.next_frame(&mut fifo)
+			{
+				break index;
+			}
+		};
+
+		// core1 has the frame until it's submitted
+		let frame = unsafe { FRAMES.get(index) };
+
+		// render the next frame one band at a time, erasing only what was drawn into it last time
+		let mut drawn = DamageList::new();
+
+		for first_row in (0..SCREEN_HEIGHT).step_by(BAND_HEIGHT)
+		{
+			let band_data = &mut frame.pixels[first_row * SCREEN_WIDTH..(first_row + BAND_HEIGHT) * SCREEN_WIDTH];
+			let mut band = Surface::new(band_data, SCREEN_WIDTH, BAND_HEIGHT).with_damage(&mut drawn, 0, first_row);
+
+			band.fill_damage(&frame.drawn, 0);
+			depthbuffer::clear(depthbuffer);
+
+			scene.render_band(&mut band, depthbuffer, first_row);
+		}
+
+		// what changed since the frame before: what it showed and what this one shows
+		frame.changed = drawn;
+		frame.changed.add_list(&shown_drawn);
+		frame.drawn = drawn;
+		shown_drawn = drawn;
+
+		renderer.submit(&mut fifo, index);
+
+		scene.update(&INPUT.load());
+	}
+}
+
+// core1: bins the triangles of a frame, then renders it strip by strip into the strips it gets
+// back from core0
+#[cfg(feature = "tiled")]
+fn render_core() -> !
+{
+	let mut fifo = multicore::core1_fifo();
+	let mut renderer = Renderer::<FRAME_COUNT>::new();
+
+	// per pixel depth of one strip, smaller is closer
+	let depthbuffer: &'static mut [u16] = cortex_m::singleton!(: [u16; SCREEN_WIDTH * STRIP_HEIGHT] = [depthbuffer::DEPTH_FAR; SCREEN_WIDTH * STRIP_HEIGHT]).unwrap();
+
+	// around 8K with 64 triangles, too much for core1's stack next to what binning a mesh needs
+	let bins: &'static mut TriangleBins<BIN_CAPACITY> = cortex_m::singleton!(: TriangleBins<BIN_CAPACITY> = TriangleBins::new(SCREEN_HEIGHT, STRIP_HEIGHT)).unwrap();
+
+	// the 3D scene covers the whole screen
+	let mut scene = Scene::new(Viewport::fullscreen());
+
+	loop
+	{
+		scene.bin(bins);
+
+		for strip in 0..bins.strip_count()
+		{
+			let index = loop
+			{
+				if let Some(index) = renderer.next_frame(&mut fifo)
+				{
+					break index;
+				}
+			};
+
+			// core1 has the strip until it's submitted
+			let frame = unsafe { FRAMES.get(index) };
+			let (first_row, rows) = bins.strip_rows(strip);
+
+			let mut framebuffer = Surface::new(&mut frame.pixels[..rows * SCREEN_WIDTH], SCREEN_WIDTH, rows);
+			framebuffer.clear();
+			depthbuffer::clear(depthbuffer);
+
+			scene.render_strip(bins, &mut framebuffer, depthbuffer, strip);
+
+			frame.first_row = first_row;
+			frame.rows = rows;
+			renderer.submit(&mut fifo, index);
+		}
+
+		scene.update(&INPUT.load());
+	}
+}
+
+#[interrupt]
+fn DMA_IRQ_0()
+{
+	dma::clear_interrupt();
+
+	cortex_m::interrupt::free(|cs| {
+		if let Some(transfer) = TRANSFER.borrow(cs).borrow_mut().as_mut()
+		{
+			// A window the display didn't take ends the transfer early. Nothing can be done about
+			// it in here, core0 gets the frame back through take_finished and the next one is
+			// sent as usual.
+			let _ = transfer.line_done();
+		}
+	});
+}
@@ -0,0 +1,46 @@
+use rp2040_hal as hal;
+use hal::pac;
+use hal::multicore::{Multicore, Stack};
+use picorender::pipeline::Fifo;
+
+// words on core1's stack, it renders with the depth buffer, framebuffers and triangle bins kept out of it
+const CORE1_STACK_WORDS: usize = 4096;
+
+// each core only sees its own end of the SIO FIFO: what core0 writes core1 reads and the other way around
+pub struct CoreFifo
+{
+	pub fifo: hal::sio::SioFifo
+}
+
+impl Fifo for CoreFifo
+{
+	fn write(&mut self, value: u32)
+	{
+		self.fifo.write_blocking(value);
+	}
+
+	fn read(&mut self) -> Option<u32>
+	{
+		self.fifo.read()
+	}
+}
+
+// starts entry on core1 with a stack of its own, can only be called once
+pub fn spawn_core1(psm: &mut pac::PSM, ppb: &mut pac::PPB, sio: &mut hal::Sio, entry: fn() -> !)
+{
+	let stack: &'static mut Stack<CORE1_STACK_WORDS> = cortex_m::singleton!(: Stack<CORE1_STACK_WORDS> = Stack::new()).unwrap();
+
+	let mut multicore = Multicore::new(psm, ppb, sio);
+	let core1 = &mut multicore.cores()[1];
+	core1.spawn(entry, &mut stack.mem).unwrap();
+}
+
+// the SIO of the core calling it, for core1 which doesn't get the peripherals from main
+pub fn core1_fifo() -> CoreFifo
+{
+	let pac = unsafe { pac::Peripherals::steal() };
+
+	CoreFifo{
+		fifo: hal::Sio::new(pac.SIO).fifo
+	}
+}
@@ -0,0 +1,103 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+use picorender::framebuffer::Surface;
+use crate::ppm::{self, Image};
+
+// Golden image checks: a rendered surface is compared pixel for pixel against a checked in PPM.
+// Running with PICOSIM_BLESS=1 overwrites the references with the current output instead.
+
+pub const BLESS_ENV: &str = "PICOSIM_BLESS";
+
+// matching pixels are kept as a dark copy of the reference so the mismatches stand out
+const DIFF_COLOR: [u8; 3] = [255, 0, 255];
+
+pub struct Mismatch
+{
+	pub differing_pixels: usize,
+	pub diff: Image
+}
+
+// None when both images are identical, images of different sizes differ everywhere
+pub fn compare(expected: &Image, actual: &Image) -> Option<Mismatch>
+{
+	if expected.width != actual.width || expected.height != actual.height
+	{
+		let mut diff = Image::new(actual.width, actual.height);
+
+		for pix in diff.data.chunks_mut(3)
+		{
+			pix.copy_from_slice(&DIFF_COLOR);
+		}
+
+		return Some(Mismatch{
+			differing_pixels: actual.width * actual.height,
+			diff
+		});
+	}
+
+	let mut diff = Image::new(actual.width, actual.height);
+	let mut differing_pixels = 0usize;
+
+	for y in 0..actual.height
+	{
+		for x in 0..actual.width
+		{
+			let expected_pix = expected.get_pixel(x, y);
+
+			if expected_pix == actual.get_pixel(x, y)
+			{
+				diff.set_pixel(x, y, expected_pix.map(|c| c / 4));
+			}
+			else
+			{
+				diff.set_pixel(x, y, DIFF_COLOR);
+				differing_pixels += 1;
+			}
+		}
+	}
+
+	if differing_pixels == 0
+	{
+		return None;
+	}
+
+	Some(Mismatch{
+		differing_pixels,
+		diff
+	})
+}
+
+// compare against reference_dir/name.ppm, on a mismatch name.actual.ppm and name.diff.ppm
+// are written to output_dir and the error points at them
+pub fn check(surface: &Surface, reference_dir: &Path, output_dir: &Path, name: &str) -> Result<(), String>
+{
+	let actual = Image::from_surface(surface);
+	let reference_path = reference_dir.join(format!("{}.ppm", name));
+
+	if env::var_os(BLESS_ENV).is_some()
+	{
+		fs::create_dir_all(reference_dir).map_err(|err| format!("can't create {}: {}", reference_dir.display(), err))?;
+		return ppm::write_file(&reference_path, &actual).map_err(|err| format!("can't write {}: {}", reference_path.display(), err));
+	}
+
+	let expected = ppm::read_file(&reference_path)
+		.map_err(|err| format!("can't read {}: {} (run with {}=1 to create it)", reference_path.display(), err, BLESS_ENV))?;
+
+	let mismatch = match compare(&expected, &actual)
+	{
+		None => return Ok(()),
+		Some(mismatch) => mismatch
+	};
+
+	let actual_path = output_dir.join(format!("{}.actual.ppm", name));
+	let diff_path = output_dir.join(format!("{}.diff.ppm", name));
+
+	fs::create_dir_all(output_dir)
+		.and_then(|_| ppm::write_file(&actual_path, &actual))
+		.and_then(|_| ppm::write_file(&diff_path, &mismatch.diff))
+		.map_err(|err| format!("{}: can't write the diff to {}: {}", name, output_dir.display(), err))?;
+
+	Err(format!("{}: {} pixels differ from {}, see {} and {}",
+		name, mismatch.differing_pixels, reference_path.display(), actual_path.display(), diff_path.display()))
+}
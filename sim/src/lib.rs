@@ -0,0 +1,4 @@
+// Image helpers for running the renderer on a desktop machine.
+
+pub mod ppm;
+pub mod golden;
@@ -0,0 +1,78 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+use picorender::{SCREEN_WIDTH, SCREEN_HEIGHT};
+use picorender::depthbuffer;
+use picorender::viewport::Viewport;
+use picorender::framebuffer::Surface;
+use picorender::scene::{Input, Scene};
+use picosim::ppm::{self, Image};
+
+// Runs the firmware scene loop without the hardware and writes every frame as out_dir/frame_NNNN.ppm
+// usage: picosim [frame count] [output directory]
+
+const DEFAULT_FRAME_COUNT: usize = 120;
+const DEFAULT_OUT_DIR: &str = "frames";
+
+// B is tapped this often so every render mode shows up
+const MODE_SWITCH_FRAMES: usize = 30;
+
+fn main()
+{
+	let args: Vec<String> = env::args().collect();
+
+	let frame_count = match args.get(1)
+	{
+		Some(arg) => arg.parse::<usize>().unwrap_or_else(|_| usage()),
+		None => DEFAULT_FRAME_COUNT
+	};
+	let out_dir = PathBuf::from(args.get(2).map(String::as_str).unwrap_or(DEFAULT_OUT_DIR));
+
+	if let Err(err) = fs::create_dir_all(&out_dir)
+	{
+		eprintln!("can't create {}: {}", out_dir.display(), err);
+		process::exit(1);
+	}
+
+	let mut framebuffer_data = vec![0u8; SCREEN_WIDTH * SCREEN_HEIGHT];
+	let mut framebuffer = Surface::new(&mut framebuffer_data, SCREEN_WIDTH, SCREEN_HEIGHT);
+	let mut depthbuffer = vec![depthbuffer::DEPTH_FAR; SCREEN_WIDTH * SCREEN_HEIGHT];
+
+	let mut scene = Scene::new(Viewport::fullscreen());
+
+	for frame in 0..frame_count
+	{
+		framebuffer.clear();
+		depthbuffer::clear(&mut depthbuffer);
+
+		scene.render(&mut framebuffer, &mut depthbuffer);
+
+		let path = out_dir.join(format!("frame_{:04}.ppm", frame));
+
+		if let Err(err) = ppm::write_file(&path, &Image::from_surface(&framebuffer))
+		{
+			eprintln!("can't write {}: {}", path.display(), err);
+			process::exit(1);
+		}
+
+		scene.update(&scripted_input(frame));
+	}
+}
+
+// A and X are held the whole time, B goes down for a single frame once every MODE_SWITCH_FRAMES
+fn scripted_input(frame: usize) -> Input
+{
+	Input{
+		a: true,
+		b: frame % MODE_SWITCH_FRAMES == MODE_SWITCH_FRAMES - 1,
+		x: true,
+		y: false
+	}
+}
+
+fn usage() -> !
+{
+	eprintln!("usage: picosim [frame count] [output directory]");
+	process::exit(2);
+}
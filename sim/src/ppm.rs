@@ -0,0 +1,127 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use picorender::framebuffer::Surface;
+use picorender::pixel_ops::expand_rgb565;
+
+// Binary PPM (P6) files, pixels go through the same RGB 565 conversion the display gets,
+// so a file shows exactly the colors the panel would.
+
+// RGB 888 pixels, 3 bytes each, row by row
+#[derive(Clone, PartialEq, Debug)]
+pub struct Image
+{
+	pub width: usize,
+	pub height: usize,
+	pub data: Vec<u8>
+}
+
+impl Image
+{
+	pub fn new(width: usize, height: usize) -> Image
+	{
+		Image{
+			width,
+			height,
+			data: vec![0u8; width * height * 3]
+		}
+	}
+
+	pub fn from_surface(surface: &Surface) -> Image
+	{
+		let mut data = Vec::with_capacity(surface.width * surface.height * 3);
+
+		for pix in surface.pixels_rgb565()
+		{
+			let (r, g, b) = expand_rgb565(pix);
+			data.extend_from_slice(&[r, g, b]);
+		}
+
+		Image{
+			width: surface.width,
+			height: surface.height,
+			data
+		}
+	}
+
+	pub fn get_pixel(&self, x: usize, y: usize) -> [u8; 3]
+	{
+		let index = (y * self.width + x) * 3;
+		[self.data[index], self.data[index + 1], self.data[index + 2]]
+	}
+
+	pub fn set_pixel(&mut self, x: usize, y: usize, pix: [u8; 3])
+	{
+		let index = (y * self.width + x) * 3;
+		self.data[index..index + 3].copy_from_slice(&pix);
+	}
+}
+
+pub fn encode(image: &Image) -> Vec<u8>
+{
+	let mut out = format!("P6\n{} {}\n255\n", image.width, image.height).into_bytes();
+	out.extend_from_slice(&image.data);
+
+	out
+}
+
+// only 8 bit files are accepted, that's all encode writes
+pub fn decode(data: &[u8]) -> Option<Image>
+{
+	let mut pos = 0usize;
+	let mut fields = [0usize; 3];
+
+	if !data.starts_with(b"P6")
+	{
+		return None;
+	}
+	pos += 2;
+
+	for field in fields.iter_mut()
+	{
+		// whitespace and # comments may sit between the header fields
+		loop
+		{
+			match data.get(pos)
+			{
+				Some(b'#') => while pos < data.len() && data[pos] != b'\n' { pos += 1; },
+				Some(c) if c.is_ascii_whitespace() => pos += 1,
+				Some(_) => break,
+				None => return None
+			}
+		}
+
+		let start = pos;
+		while pos < data.len() && data[pos].is_ascii_digit()
+		{
+			pos += 1;
+		}
+
+		*field = std::str::from_utf8(&data[start..pos]).ok()?.parse().ok()?;
+	}
+
+	// exactly one whitespace byte separates the header from the pixels
+	let [width, height, max_value] = fields;
+	let pixels = data.get(pos + 1..)?;
+
+	if max_value != 255 || pixels.len() != width * height * 3
+	{
+		return None;
+	}
+
+	Some(Image{
+		width,
+		height,
+		data: pixels.to_vec()
+	})
+}
+
+pub fn write_file<P: AsRef<Path>>(path: P, image: &Image) -> io::Result<()>
+{
+	fs::write(path, encode(image))
+}
+
+pub fn read_file<P: AsRef<Path>>(path: P) -> io::Result<Image>
+{
+	decode(&fs::read(path)?).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "not an 8 bit binary PPM"))
+}
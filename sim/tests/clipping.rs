@@ -0,0 +1,99 @@
+use picorender::framebuffer::Surface;
+use picorender::line::LineStyle;
+use picorender::vector::vec2f;
+
+// A window into a bigger canvas, every line drawn into the window has to hit exactly the pixels
+// the same line drawn on the whole canvas hits inside the window.
+const CANVAS: usize = 96;
+const WINDOW_X: usize = 32;
+const WINDOW_Y: usize = 24;
+const WINDOW_WIDTH: usize = 30;
+const WINDOW_HEIGHT: usize = 20;
+
+struct Lcg(u64);
+
+impl Lcg
+{
+	fn next(&mut self, range: i32) -> i32
+	{
+		self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+		((self.0 >> 33) % range as u64) as i32
+	}
+}
+
+fn check_line(x0: i32, y0: i32, x1: i32, y1: i32, style: &LineStyle)
+{
+	let mut canvas_data = vec![0u8; CANVAS * CANVAS];
+	let mut canvas = Surface::new(&mut canvas_data, CANVAS, CANVAS);
+	canvas.draw_line_int(x0, y0, x1, y1, style, 1);
+
+	let mut window_data = vec![0u8; WINDOW_WIDTH * WINDOW_HEIGHT];
+	let mut window = Surface::new(&mut window_data, WINDOW_WIDTH, WINDOW_HEIGHT);
+	window.draw_line_int(x0 - WINDOW_X as i32, y0 - WINDOW_Y as i32, x1 - WINDOW_X as i32, y1 - WINDOW_Y as i32, style, 1);
+
+	for y in 0..WINDOW_HEIGHT
+	{
+		for x in 0..WINDOW_WIDTH
+		{
+			assert_eq!(window_data[y * WINDOW_WIDTH + x], canvas_data[(y + WINDOW_Y) * CANVAS + x + WINDOW_X],
+				"line ({}, {}) - ({}, {}) at ({}, {})", x0, y0, x1, y1, x + WINDOW_X, y + WINDOW_Y);
+		}
+	}
+}
+
+#[test]
+fn clipped_lines_keep_their_pixels()
+{
+	let mut random = Lcg(42);
+	let dashed = LineStyle::solid().with_dash(0b0110_1111, 8);
+	let thick = LineStyle::solid().with_thickness(3);
+
+	for _ in 0..3000
+	{
+		let (x0, y0, x1, y1) = (random.next(CANVAS as i32), random.next(CANVAS as i32), random.next(CANVAS as i32), random.next(CANVAS as i32));
+
+		check_line(x0, y0, x1, y1, &LineStyle::solid());
+		check_line(x0, y0, x1, y1, &dashed);
+		check_line(x0, y0, x1, y1, &thick);
+	}
+
+	// diagonals, and horizontal and vertical lines through the window
+	check_line(0, 0, 95, 95, &LineStyle::solid());
+	check_line(95, 0, 0, 95, &LineStyle::solid());
+	check_line(0, 30, 95, 30, &dashed);
+	check_line(40, 95, 40, 0, &dashed);
+}
+
+// endpoints as far out as draw_line_styled's saturating float to int conversion puts them
+#[test]
+fn lines_across_the_whole_i32_range_are_clipped()
+{
+	let mut data = vec![0u8; WINDOW_WIDTH * WINDOW_HEIGHT];
+	let mut surface = Surface::new(&mut data, WINDOW_WIDTH, WINDOW_HEIGHT);
+	let dashed = LineStyle::solid().with_dash(0b0110_1111, 8);
+
+	// horizontal through row 5 and vertical through column 7
+	surface.draw_line_int(i32::MIN, 5, i32::MAX, 5, &LineStyle::solid(), 1);
+	surface.draw_line_int(7, i32::MAX, 7, i32::MIN, &LineStyle::solid(), 2);
+
+	for x in 0..WINDOW_WIDTH
+	{
+		assert_eq!(data[5 * WINDOW_WIDTH + x], if x == 7 { 2 } else { 1 });
+	}
+	for y in 0..WINDOW_HEIGHT
+	{
+		assert_eq!(data[y * WINDOW_WIDTH + 7], 2);
+	}
+
+	// the diagonals from corner to corner of the i32 range go through the origin
+	let mut data = vec![0u8; WINDOW_WIDTH * WINDOW_HEIGHT];
+	let mut surface = Surface::new(&mut data, WINDOW_WIDTH, WINDOW_HEIGHT);
+	surface.draw_line_int(i32::MIN + 1, i32::MIN + 1, i32::MAX, i32::MAX, &LineStyle::solid().with_thickness(3), 1);
+	surface.draw_line_int(i32::MAX, i32::MIN, i32::MIN, i32::MAX, &dashed, 1);
+	surface.draw_line_styled(&vec2f{x: -1.0e12f32, y: -1.0e12f32}, &vec2f{x: 1.0e12f32, y: 1.0e12f32}, &LineStyle::solid(), 1);
+
+	for i in 0..WINDOW_HEIGHT
+	{
+		assert_eq!(data[i * WINDOW_WIDTH + i], 1, "({}, {})", i, i);
+	}
+}
@@ -0,0 +1,141 @@
+use picorender::damage::{DamageList, DamageRect, MAX_DAMAGE_RECTS};
+use picorender::depthbuffer;
+use picorender::viewport::Viewport;
+use picorender::framebuffer::Surface;
+use picorender::scene::Scene;
+use picorender::pixel_ops::colors;
+
+const WIDTH: usize = 120;
+const HEIGHT: usize = 160;
+
+#[test]
+fn near_rectangles_merge()
+{
+	let mut damage = DamageList::new();
+	damage.add(DamageRect::new(10, 10, 5, 5).unwrap());
+	// a few pixels to the right, cheaper as part of the first one
+	damage.add(DamageRect::new(18, 12, 4, 4).unwrap());
+	// far away
+	damage.add(DamageRect::new(80, 100, 2, 2).unwrap());
+
+	assert_eq!(damage.rects(), [
+		DamageRect{left: 10, top: 10, right: 21, bottom: 15},
+		DamageRect{left: 80, top: 100, right: 81, bottom: 101}
+	]);
+
+	// already covered
+	damage.add_pixel(12, 14);
+	assert_eq!(damage.rects().len(), 2);
+
+	// bridges the gap, everything ends up in one rectangle
+	damage.add(DamageRect::new(25, 20, 50, 75).unwrap());
+	assert_eq!(damage.rects(), [DamageRect{left: 10, top: 10, right: 81, bottom: 101}]);
+
+	assert!(DamageRect::new(5, 5, 0, 3).is_none());
+	damage.add_span(3, 7, 7);
+	assert_eq!(damage.rects().len(), 1);
+
+	damage.clear();
+	assert!(damage.is_empty());
+}
+
+#[test]
+fn full_list_grows_the_closest_rectangle()
+{
+	let mut damage = DamageList::new();
+
+	// a row of single pixels too far apart to merge
+	for i in 0..MAX_DAMAGE_RECTS
+	{
+		damage.add_pixel(i * 20, 0);
+	}
+	assert_eq!(damage.rects().len(), MAX_DAMAGE_RECTS);
+
+	damage.add_pixel(41, 30);
+
+	assert_eq!(damage.rects().len(), MAX_DAMAGE_RECTS);
+	assert!(damage.rects().contains(&DamageRect{left: 40, top: 0, right: 41, bottom: 30}));
+	assert_eq!(damage.area(), MAX_DAMAGE_RECTS - 1 + 2 * 31);
+}
+
+#[test]
+fn surfaces_record_what_they_draw()
+{
+	let mut data = vec![0u8; WIDTH * HEIGHT];
+	let mut damage = DamageList::new();
+
+	{
+		// the bottom half of a screen
+		let mut surface = Surface::new(&mut data, WIDTH, HEIGHT / 2).with_damage(&mut damage, 0, HEIGHT / 2);
+
+		surface.draw_pixel(3, 4, colors::WHITE as u8);
+		surface.draw_line_horizontal(40.0f32, 50.0f32, 60.0f32, colors::RED as u8);
+		// off the surface, nothing drawn and nothing recorded
+		surface.draw_pixel(WIDTH, 4, colors::WHITE as u8);
+
+		let mut window = surface.sub_surface(100, 20, 10, 10);
+		window.fill(colors::BLUE as u8);
+	}
+
+	assert_eq!(damage.rects(), [
+		DamageRect{left: 3, top: 84, right: 3, bottom: 84},
+		DamageRect{left: 50, top: 120, right: 59, bottom: 120},
+		DamageRect{left: 100, top: 100, right: 109, bottom: 109}
+	]);
+
+	// erasing by damage clears exactly what was drawn
+	let mut surface = Surface::new(&mut data, WIDTH, HEIGHT / 2);
+	surface.origin_y = HEIGHT / 2;
+	surface.fill_damage(&damage, 0);
+	assert!(data.iter().all(|pix| *pix == 0));
+}
+
+// The firmware's main loop: two framebuffers erased by what was drawn into them last time and a
+// display that only gets the regions drawn in the frame it shows or the one replacing it.
+// Whatever the display ends up showing has to be the same as a full render.
+#[test]
+fn damage_updates_match_full_frames()
+{
+	let mut scene = Scene::new(Viewport::new(0.0f32, 0.0f32, WIDTH as f32, HEIGHT as f32));
+	let mut depth = vec![depthbuffer::DEPTH_FAR; WIDTH * HEIGHT];
+
+	let mut buffers = [vec![0u8; WIDTH * HEIGHT], vec![0u8; WIDTH * HEIGHT]];
+	let mut drawn = [DamageList::new(), DamageList::new()];
+	let mut display = vec![0u8; WIDTH * HEIGHT];
+
+	for frame in 0..12
+	{
+		scene.degrees_x = frame as f32 * 0.3f32;
+		scene.degrees_y = frame as f32 * 0.45f32;
+
+		let back = frame % 2;
+		let mut damage = DamageList::new();
+		{
+			let mut framebuffer = Surface::new(&mut buffers[back], WIDTH, HEIGHT).with_damage(&mut damage, 0, 0);
+			framebuffer.fill_damage(&drawn[back], 0);
+			depthbuffer::clear(&mut depth);
+			scene.render(&mut framebuffer, &mut depth);
+		}
+
+		let mut changed = damage;
+		changed.add_list(&drawn[1 - back]);
+		drawn[back] = damage;
+
+		for rect in changed.rects()
+		{
+			for y in rect.top..=rect.bottom
+			{
+				let row = y * WIDTH;
+				display[row + rect.left..=row + rect.right].copy_from_slice(&buffers[back][row + rect.left..=row + rect.right]);
+			}
+		}
+
+		let mut full_data = vec![0u8; WIDTH * HEIGHT];
+		let mut full = Surface::new(&mut full_data, WIDTH, HEIGHT);
+		depthbuffer::clear(&mut depth);
+		scene.render(&mut full, &mut depth);
+
+		assert!(display == full_data, "frame {}", frame);
+		assert!(changed.area() < WIDTH * HEIGHT, "frame {} sent the whole screen", frame);
+	}
+}
@@ -0,0 +1,707 @@
+use std::path::{Path, PathBuf};
+use picorender::vector::{vec2f, vec3f};
+use picorender::matrix::{mat3x3, mat4x4};
+use picorender::pixel_ops::colors;
+use picorender::depthbuffer;
+use picorender::viewport::Viewport;
+use picorender::framebuffer::Surface;
+use picorender::model::{self, triangle, triangle3d};
+use picorender::light::DirectionalLight;
+use picorender::scene::{RenderMode, Scene};
+use picorender::tiles::TriangleBins;
+use picorender::text::{print_text, print_text_styled, print_text_transformed, print_text_3d, print_text_billboard, StrokeFont};
+use picorender::font::{Font, draw_text};
+use picorender::bitmap_font::{FONT_5X7, FONT_8X8, FONT_8X16};
+use picorender::console::Console;
+use picorender::layout::{Align, TextLayout, TextRect, draw_text_layout, measure_text};
+use picorender::line::LineStyle;
+use picorender::shapes::FillRule;
+use picorender::graphics::Rgb332;
+use picosim::golden;
+use embedded_graphics::prelude::*;
+use embedded_graphics::image::{Image as RawImage, ImageRaw};
+use embedded_graphics::mono_font::{ascii::FONT_6X10, MonoTextStyle};
+use embedded_graphics::pixelcolor::{BinaryColor, Rgb888};
+use embedded_graphics::primitives::{PrimitiveStyleBuilder, RoundedRectangle, Rectangle, Circle, Triangle};
+use embedded_graphics::text::Text;
+use picosim::ppm::{self, Image};
+
+// Reference images live in tests/golden, a failing test leaves <name>.actual.ppm and <name>.diff.ppm
+// in the cargo test tmp directory. Regenerate the references with PICOSIM_BLESS=1 cargo test -p picosim
+
+// half the panel resolution keeps the reference files small
+const WIDTH: usize = 120;
+const HEIGHT: usize = 160;
+
+fn reference_dir() -> PathBuf
+{
+	Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("golden")
+}
+
+fn output_dir() -> PathBuf
+{
+	Path::new(env!("CARGO_TARGET_TMPDIR")).join("golden")
+}
+
+// clear buffers of the test size are handed to draw, the result has to match tests/golden/name.ppm
+fn assert_golden<F: FnOnce(&mut Surface, &mut [u16])>(name: &str, draw: F)
+{
+	let mut framebuffer_data = vec![0u8; WIDTH * HEIGHT];
+	let mut framebuffer = Surface::new(&mut framebuffer_data, WIDTH, HEIGHT);
+	let mut depthbuffer = vec![depthbuffer::DEPTH_FAR; WIDTH * HEIGHT];
+
+	draw(&mut framebuffer, &mut depthbuffer);
+
+	if let Err(err) = golden::check(&framebuffer, &reference_dir(), &output_dir(), name)
+	{
+		panic!("{}", err);
+	}
+}
+
+// the scene every cube golden is rendered from, whole screen, in strips or in bands
+fn test_scene(render_mode: RenderMode) -> Scene
+{
+	let mut scene = Scene::new(Viewport::new(0.0f32, 0.0f32, WIDTH as f32, HEIGHT as f32));
+	scene.degrees_x = 0.6f32;
+	scene.degrees_y = 0.8f32;
+	scene.degrees_z = 0.2f32;
+	scene.render_mode = render_mode;
+
+	scene
+}
+
+fn render_scene(framebuffer: &mut Surface, depthbuffer: &mut [u16], render_mode: RenderMode)
+{
+	test_scene(render_mode).render(framebuffer, depthbuffer);
+}
+
+#[test]
+fn cube_wireframe()
+{
+	assert_golden("cube_wireframe", |framebuffer, depthbuffer| render_scene(framebuffer, depthbuffer, RenderMode::Wireframe));
+}
+
+#[test]
+fn cube_flat()
+{
+	assert_golden("cube_flat", |framebuffer, depthbuffer| render_scene(framebuffer, depthbuffer, RenderMode::Flat));
+}
+
+#[test]
+fn cube_smooth()
+{
+	assert_golden("cube_smooth", |framebuffer, depthbuffer| render_scene(framebuffer, depthbuffer, RenderMode::Smooth));
+}
+
+#[test]
+fn cube_textured()
+{
+	assert_golden("cube_textured", |framebuffer, depthbuffer| render_scene(framebuffer, depthbuffer, RenderMode::Textured));
+}
+
+// Tiled rendering into one strip buffer and one strip depth buffer, every strip is copied into
+// the framebuffer like the firmware sends it to the display. The last strip is shorter.
+fn render_scene_in_strips(framebuffer: &mut Surface, render_mode: RenderMode)
+{
+	let scene = test_scene(render_mode);
+
+	let strip_height = 24;
+	let mut bins = TriangleBins::<64>::new(HEIGHT, strip_height);
+	scene.bin(&mut bins);
+	assert_eq!(bins.dropped(), 0);
+	assert_eq!(bins.strip_count(), 7);
+
+	let mut strip_data = vec![0u8; WIDTH * strip_height];
+	let mut strip_depthbuffer = vec![depthbuffer::DEPTH_FAR; WIDTH * strip_height];
+
+	for strip in 0..bins.strip_count()
+	{
+		let (first_row, rows) = bins.strip_rows(strip);
+		let mut strip_buffer = Surface::new(&mut strip_data[..WIDTH * rows], WIDTH, rows);
+		strip_buffer.clear();
+		depthbuffer::clear(&mut strip_depthbuffer);
+
+		scene.render_strip(&bins, &mut strip_buffer, &mut strip_depthbuffer, strip);
+
+		framebuffer.data[first_row * WIDTH..(first_row + rows) * WIDTH].copy_from_slice(&strip_data[..WIDTH * rows]);
+	}
+}
+
+#[test]
+fn cube_wireframe_in_strips()
+{
+	assert_golden("cube_wireframe", |framebuffer, _| render_scene_in_strips(framebuffer, RenderMode::Wireframe));
+}
+
+#[test]
+fn cube_flat_in_strips()
+{
+	assert_golden("cube_flat", |framebuffer, _| render_scene_in_strips(framebuffer, RenderMode::Flat));
+}
+
+#[test]
+fn cube_smooth_in_strips()
+{
+	assert_golden("cube_smooth", |framebuffer, _| render_scene_in_strips(framebuffer, RenderMode::Smooth));
+}
+
+#[test]
+fn cube_textured_in_strips()
+{
+	assert_golden("cube_textured", |framebuffer, _| render_scene_in_strips(framebuffer, RenderMode::Textured));
+}
+
+// the camera sits inside the cube's bounds, faces get cut by the near plane and the screen edges
+#[test]
+fn cube_near_plane()
+{
+	assert_golden("cube_near_plane", |framebuffer, depthbuffer| {
+		let viewport = Viewport::new(0.0f32, 0.0f32, WIDTH as f32, HEIGHT as f32);
+		let projection_mat = mat4x4::new_projection(90.0f32, viewport.aspect_ratio(), 0.1f32, 100.0f32);
+
+		let mut world_to_camera_mat = mat4x4::new();
+		world_to_camera_mat.data[3][2] = -8.0f32;
+
+		let model_mat = mat4x4::new_rot_y(0.5f32) * &mat4x4::new_rot_x(0.3f32);
+		let mvp_matrix = projection_mat * &(world_to_camera_mat * &model_mat);
+		let light = DirectionalLight::new(vec3f{x: 1.0f32, y: -1.0f32, z: -1.0f32}, 0.2f32);
+
+		model::cube::new().with_culling(model::Culling::None).scale(10.0f32)
+			.rasterize_lit(framebuffer, depthbuffer, &model_mat, &mvp_matrix, &viewport, &light, colors::WHITE as u8);
+	});
+}
+
+// two half height bands sharing one half height depth buffer end up the same as the whole screen at once
+#[test]
+fn cube_flat_in_bands()
+{
+	assert_golden("cube_flat", |framebuffer, _| {
+		let scene = test_scene(RenderMode::Flat);
+
+		let band_height = HEIGHT / 2;
+		let mut band_depthbuffer = vec![depthbuffer::DEPTH_FAR; WIDTH * band_height];
+
+		for first_row in [0, band_height]
+		{
+			let mut band = Surface::new(&mut framebuffer.data[first_row * WIDTH..(first_row + band_height) * WIDTH], WIDTH, band_height);
+			depthbuffer::clear(&mut band_depthbuffer);
+			scene.render_band(&mut band, &mut band_depthbuffer, first_row);
+		}
+	});
+}
+
+#[test]
+fn cube_wireframe_anti_aliased()
+{
+	assert_golden("cube_wireframe_anti_aliased", |framebuffer, _| {
+		let viewport = Viewport::new(0.0f32, 0.0f32, WIDTH as f32, HEIGHT as f32);
+		let projection_mat = mat4x4::new_projection(90.0f32, viewport.aspect_ratio(), 0.1f32, 100.0f32);
+
+		let mut world_to_camera_mat = mat4x4::new();
+		world_to_camera_mat.data[3][2] = -40.0f32;
+
+		let model_mat = mat4x4::new_rot_y(0.8f32) * &mat4x4::new_rot_x(0.6f32);
+		let mvp_matrix = projection_mat * &(world_to_camera_mat * &model_mat);
+
+		model::cube::new().scale(10.0f32)
+			.rasterize_wireframe(framebuffer, &mvp_matrix, &viewport, &LineStyle::solid().with_anti_aliasing(true), colors::WHITE as u8);
+	});
+}
+
+#[test]
+fn text()
+{
+	assert_golden("text", |framebuffer, _| {
+		print_text(framebuffer, b"ABCDEFG", vec2f{x: 4.0f32, y: 20.0f32}, 15.0f32, colors::WHITE as u8);
+		print_text(framebuffer, b"HIJKLMN", vec2f{x: 4.0f32, y: 45.0f32}, 15.0f32, colors::RED as u8);
+		print_text(framebuffer, b"OPQRSTU", vec2f{x: 4.0f32, y: 70.0f32}, 15.0f32, colors::GREEN as u8);
+		print_text(framebuffer, b"VWXYZ", vec2f{x: 4.0f32, y: 95.0f32}, 15.0f32, colors::BLUE as u8);
+		print_text(framebuffer, b"0123456789", vec2f{x: 4.0f32, y: 120.0f32}, 11.0f32, colors::WHITE as u8);
+		// partly outside the surface on every side
+		print_text(framebuffer, b"EDGE", vec2f{x: -10.0f32, y: 10.0f32}, 20.0f32, colors::WHITE as u8);
+		print_text(framebuffer, b"EDGE", vec2f{x: 90.0f32, y: 170.0f32}, 20.0f32, colors::WHITE as u8);
+	});
+}
+
+// every printable ASCII character, 16 to a row
+#[test]
+fn text_ascii()
+{
+	assert_golden("text_ascii", |framebuffer, _| {
+		let chars: Vec<u8> = (b' '..=b'~').collect();
+
+		for (row, line) in chars.chunks(16).enumerate()
+		{
+			print_text(framebuffer, line, vec2f{x: 2.0f32, y: 14.0f32 + row as f32 * 14.0f32}, 7.2f32, colors::WHITE as u8);
+		}
+
+		print_text(framebuffer, b"Hello, World!", vec2f{x: 2.0f32, y: 110.0f32}, 9.0f32, colors::GREEN as u8);
+		print_text(framebuffer, b"fps: 30 (ok)", vec2f{x: 2.0f32, y: 130.0f32}, 9.0f32, colors::RED as u8);
+		print_text(framebuffer, b"jumpy dogs?", vec2f{x: 2.0f32, y: 150.0f32}, 9.0f32, colors::BLUE as u8);
+	});
+}
+
+// rotated, sheared and stretched text, all with the left end of the baseline at the same place
+#[test]
+fn text_transformed()
+{
+	assert_golden("text_transformed", |framebuffer, _| {
+		let center = vec2f{x: 60.0f32, y: 56.0f32};
+
+		// a translation alone is the same as print_text
+		print_text(framebuffer, b"print", vec2f{x: 4.0f32, y: 150.0f32}, 10.0f32, colors::RED as u8);
+		print_text_transformed(framebuffer, b"print", &mat3x3::new_translation(vec2f{x: 4.0f32, y: 150.0f32}), 10.0f32, &LineStyle::solid(), colors::WHITE as u8);
+
+		for i in 0..8
+		{
+			// spaced out from the center so the words don't overlap
+			let transform = (mat3x3::new_translation(center) * &mat3x3::new_rotation(i as f32 * core::f32::consts::PI / 4.0f32)) * &mat3x3::new_translation(vec2f{x: 10.0f32, y: 3.0f32});
+			print_text_transformed(framebuffer, b"spin", &transform, 9.0f32, &LineStyle::solid(), colors::WHITE as u8);
+		}
+
+		let italic = mat3x3::new_translation(vec2f{x: 4.0f32, y: 118.0f32}) * &mat3x3::new_shear(-0.4f32, 0.0f32);
+		print_text_transformed(framebuffer, b"Italic", &italic, 12.0f32, &LineStyle::solid(), colors::GREEN as u8);
+
+		let stretched = mat3x3::new_translation(vec2f{x: 4.0f32, y: 138.0f32}) * &mat3x3::new_scale(2.0f32, 0.75f32);
+		print_text_transformed(framebuffer, b"wide", &stretched, 7.0f32, &LineStyle::solid(), colors::WHITE as u8);
+	});
+}
+
+// a label on the front face of a spinning cube and billboards on two of its corners
+#[test]
+fn text_3d()
+{
+	assert_golden("text_3d", |framebuffer, _| {
+		let viewport = Viewport::new(0.0f32, 0.0f32, WIDTH as f32, HEIGHT as f32);
+		let projection_mat = mat4x4::new_projection(90.0f32, viewport.aspect_ratio(), 0.1f32, 100.0f32);
+
+		let mut world_to_camera_mat = mat4x4::new();
+		world_to_camera_mat.data[3][2] = -40.0f32;
+
+		let model_mat = mat4x4::new_rot_y(0.7f32) * &mat4x4::new_rot_x(0.3f32);
+		let mvp_matrix = projection_mat * &(world_to_camera_mat * &model_mat);
+
+		model::cube::new().scale(10.0f32).rasterize_wireframe(framebuffer, &mvp_matrix, &viewport, &LineStyle::solid(), colors::BLUE as u8);
+
+		// centered on the +z face, pushed out a little so it's on top of it
+		let mut label_mat = mat4x4::new();
+		label_mat.data[3][0] = -7.6f32;
+		label_mat.data[3][1] = -1.6f32;
+		label_mat.data[3][2] = 10.0f32;
+		print_text_3d(framebuffer, b"RUST", &(mvp_matrix * &label_mat), &viewport, 4.0f32, &LineStyle::solid(), colors::WHITE as u8);
+
+		// the same label on the -x face, seen at an angle
+		let side_mat = mat4x4::new_rot_y(-core::f32::consts::FRAC_PI_2);
+		let mut side_label_mat = mat4x4::new();
+		side_label_mat.data[3][0] = -5.6f32;
+		side_label_mat.data[3][1] = -1.6f32;
+		side_label_mat.data[3][2] = 10.0f32;
+		print_text_3d(framebuffer, b"PICO", &(mvp_matrix * &(side_mat * &side_label_mat)), &viewport, 3.0f32, &LineStyle::solid(), colors::GREEN as u8);
+
+		print_text_billboard(framebuffer, b"top", &vec3f{x: 10.0f32, y: 10.0f32, z: 10.0f32}, &mvp_matrix, &viewport, 7.0f32, &LineStyle::solid(), colors::RED as u8);
+		print_text_billboard(framebuffer, b"bottom", &vec3f{x: -10.0f32, y: -10.0f32, z: -10.0f32}, &mvp_matrix, &viewport, 7.0f32, &LineStyle::solid(), colors::RED as u8);
+		// behind the camera, skipped
+		print_text_billboard(framebuffer, b"hidden", &vec3f{x: 0.0f32, y: 0.0f32, z: 60.0f32}, &mvp_matrix, &viewport, 7.0f32, &LineStyle::solid(), colors::RED as u8);
+	});
+}
+
+// the three bitmap fonts, with and without a background and scaled up, next to the stroke font
+#[test]
+fn bitmap_text()
+{
+	assert_golden("bitmap_text", |framebuffer, _| {
+		framebuffer.fill_rect(0, 0, 120, 160, colors::BLUE as u8);
+		// stripes show which parts of a cell the background covers
+		for x in (0..120).step_by(8)
+		{
+			framebuffer.fill_rect(x, 0, 4, 160, Rgb332::new(0, 0, 1).0);
+		}
+
+		let chars: Vec<u8> = (b' '..=b'~').collect();
+
+		for (row, line) in chars.chunks(24).enumerate()
+		{
+			draw_text(framebuffer, &FONT_5X7, line, vec2f{x: 0.0f32, y: row as f32 * 8.0f32}, colors::WHITE as u8);
+		}
+
+		draw_text(framebuffer, &FONT_8X8, b"Hello, 8x8", vec2f{x: 0.0f32, y: 34.0f32}, Rgb332::YELLOW.0);
+		draw_text(framebuffer, &FONT_8X8.with_background(Some(colors::BLACK as u8)), b"bg: {ok}", vec2f{x: 0.0f32, y: 44.0f32}, colors::GREEN as u8);
+		draw_text(framebuffer, &FONT_8X16, b"8x16 jpqy", vec2f{x: 0.0f32, y: 54.0f32}, colors::WHITE as u8);
+		draw_text(framebuffer, &FONT_8X16.with_background(Some(colors::RED as u8)), b"Ag_|", vec2f{x: 76.0f32, y: 54.0f32}, colors::WHITE as u8);
+		draw_text(framebuffer, &FONT_5X7.with_scale(3), b"x3!", vec2f{x: 0.0f32, y: 72.0f32}, colors::WHITE as u8);
+		draw_text(framebuffer, &FONT_8X8.with_scale(2).with_background(Some(colors::BLACK as u8)), b"2x", vec2f{x: 60.0f32, y: 72.0f32}, colors::RED as u8);
+
+		// the same line through the Font trait, baselines lined up using the ascent
+		let stroke = StrokeFont::new(10.0f32);
+		let bitmap = FONT_8X8;
+		let baseline = 120.0f32;
+		draw_text(framebuffer, &stroke, b"Font", vec2f{x: 0.0f32, y: baseline - stroke.ascent()}, colors::WHITE as u8);
+		draw_text(framebuffer, &bitmap, b"Font", vec2f{x: 44.0f32, y: baseline - bitmap.ascent()}, colors::WHITE as u8);
+		framebuffer.draw_line(&vec2f{x: 0.0f32, y: baseline}, &vec2f{x: 119.0f32, y: baseline}, colors::RED as u8);
+
+		// chars outside the atlas are blank, partly off screen is clipped
+		draw_text(framebuffer, &FONT_8X16.with_background(Some(colors::BLACK as u8)), &[0x7f, 0xff, b'E', b'D', b'G', b'E'], vec2f{x: 80.0f32, y: 150.0f32}, colors::WHITE as u8);
+		draw_text(framebuffer, &FONT_8X8.with_scale(2), b"EDGE", vec2f{x: -6.0f32, y: 134.0f32}, Rgb332::YELLOW.0);
+	});
+}
+
+// outline just around a laid out text block
+fn draw_bounds(framebuffer: &mut Surface, bounds: &TextRect, color: u8)
+{
+	let x = bounds.x.floor() as i32 - 1;
+	let y = bounds.y.floor() as i32 - 1;
+	framebuffer.draw_rect(x, y, bounds.width.ceil() as i32 + 2, bounds.height.ceil() as i32 + 2, color);
+}
+
+// alignment, newlines, wrapping and clipping to a box, each with its bounding box around it
+#[test]
+fn text_layout()
+{
+	assert_golden("text_layout", |framebuffer, _| {
+		let text: &[u8] = b"The quick brown fox jumps over the lazy dog";
+		let bitmap = FONT_5X7;
+		let stroke = StrokeFont::new(9.0f32).with_proportional(true);
+
+		// wrapped into a 56 pixel wide column, one per alignment
+		for (i, align) in [Align::Left, Align::Center, Align::Right].into_iter().enumerate()
+		{
+			let layout = TextLayout::new().with_align(align).with_wrap_width(Some(56.0f32)).with_line_spacing(1.0f32);
+			let position = vec2f{x: 2.0f32 + (i % 2) as f32 * 60.0f32, y: 2.0f32 + (i / 2) as f32 * 42.0f32};
+			let bounds = draw_text_layout(framebuffer, &bitmap, &layout, text, position, colors::WHITE as u8);
+			assert_eq!(bounds, measure_text(&bitmap, &layout, text, position));
+			draw_bounds(framebuffer, &bounds, colors::RED as u8);
+		}
+
+		// anchored on the center line of the surface, lines split by '\n'
+		let layout = TextLayout::new().with_align(Align::Center);
+		let bounds = draw_text_layout(framebuffer, &stroke, &layout, b"Center\nof the\nsurface", vec2f{x: 90.0f32, y: 46.0f32}, colors::GREEN as u8);
+		draw_bounds(framebuffer, &bounds, colors::RED as u8);
+
+		// anchored on the right edge, with an empty line in between
+		let layout = TextLayout::new().with_align(Align::Right).with_line_spacing(3.0f32);
+		let bounds = draw_text_layout(framebuffer, &FONT_8X8, &layout, b"right\n\nedge", vec2f{x: 118.0f32, y: 88.0f32}, colors::WHITE as u8);
+		draw_bounds(framebuffer, &bounds, colors::RED as u8);
+
+		// a box that only has room for two lines, the overly long word gets broken
+		let layout = TextLayout::in_rect(60.0f32, 24.0f32).with_align(Align::Center);
+		framebuffer.draw_rect(1, 129, 62, 26, colors::BLUE as u8);
+		let bounds = draw_text_layout(framebuffer, &stroke, &layout, b"Antidisestablishment is long", vec2f{x: 2.0f32, y: 130.0f32}, colors::WHITE as u8);
+		draw_bounds(framebuffer, &bounds, colors::RED as u8);
+	});
+}
+
+// write!() into consoles, wrapping long lines and scrolling once they're full
+#[test]
+fn console()
+{
+	assert_golden("console", |framebuffer, _| {
+		use std::fmt::Write;
+
+		let rect = TextRect{x: 2.0f32, y: 2.0f32, width: 116.0f32, height: 60.0f32};
+		let mut console: Console<_, 16, 32> = Console::new(FONT_5X7, rect, colors::WHITE as u8);
+
+		// more lines than fit, the first ones scroll away
+		for frame in 0..12
+		{
+			writeln!(console, "frame {:3} {:.2} ms", frame, frame as f32 * 1.5f32).unwrap();
+		}
+		// overwritten by what comes after the \r
+		write!(console, "fps 0\rfps {}", 60).unwrap();
+		assert_eq!(console.row_count(), 8);
+		assert_eq!(console.cursor(), (7, 6));
+		console.draw(framebuffer);
+		framebuffer.draw_rect(1, 1, 118, 62, colors::BLUE as u8);
+
+		let rect = TextRect{x: 2.0f32, y: 68.0f32, width: 116.0f32, height: 48.0f32};
+		let mut console: Console<_, 8, 48> = Console::new(FONT_8X8, rect, colors::GREEN as u8);
+		write!(console, "a line that is far too long for the console wraps, {} {:?} {:x}", -42, Some(1.25f32), 0xBEEFu16).unwrap();
+		write!(console, "\nnot ascii: \u{e9}\u{1f980}").unwrap();
+		console.draw(framebuffer);
+		framebuffer.draw_rect(1, 67, 118, 50, colors::BLUE as u8);
+
+		let rect = TextRect{x: 2.0f32, y: 122.0f32, width: 116.0f32, height: 36.0f32};
+		let mut console: Console<_, 4, 32> = Console::new(StrokeFont::new(8.0f32), rect, colors::RED as u8);
+		for i in 0..5
+		{
+			write!(console, "{}: stroke {} ", i, i * i).unwrap();
+		}
+		console.draw(framebuffer);
+		framebuffer.draw_rect(1, 121, 118, 38, colors::BLUE as u8);
+	});
+}
+
+#[test]
+fn lines()
+{
+	assert_golden("lines", |framebuffer, _| {
+		let center = vec2f{x: 60.0f32, y: 80.0f32};
+
+		// a fan covering every octant, including the exactly horizontal and vertical ones
+		for i in 0..16
+		{
+			let end = vec2f{x: 50.0f32, y: 0.0f32}.rotate(i as f32 * core::f32::consts::PI / 8.0f32).translate(center);
+			framebuffer.draw_line(&center, &end, colors::WHITE as u8);
+		}
+
+		// lines running off the surface
+		framebuffer.draw_line(&vec2f{x: -20.0f32, y: 5.0f32}, &vec2f{x: 140.0f32, y: 15.0f32}, colors::RED as u8);
+		framebuffer.draw_line(&vec2f{x: 5.0f32, y: -20.0f32}, &vec2f{x: 15.0f32, y: 180.0f32}, colors::GREEN as u8);
+	});
+}
+
+#[test]
+fn lines_degenerate()
+{
+	assert_golden("lines_degenerate", |framebuffer, _| {
+		// exactly vertical and horizontal, both endpoints included
+		framebuffer.draw_line(&vec2f{x: 10.0f32, y: 10.0f32}, &vec2f{x: 10.0f32, y: 150.0f32}, colors::WHITE as u8);
+		framebuffer.draw_line(&vec2f{x: 110.0f32, y: 150.0f32}, &vec2f{x: 110.0f32, y: 10.0f32}, colors::WHITE as u8);
+		framebuffer.draw_line(&vec2f{x: 20.0f32, y: 10.0f32}, &vec2f{x: 100.0f32, y: 10.0f32}, colors::RED as u8);
+		framebuffer.draw_line(&vec2f{x: 100.0f32, y: 150.0f32}, &vec2f{x: 20.0f32, y: 150.0f32}, colors::RED as u8);
+
+		// a single pixel
+		framebuffer.draw_line(&vec2f{x: 60.0f32, y: 80.0f32}, &vec2f{x: 60.0f32, y: 80.0f32}, colors::GREEN as u8);
+
+		// completely outside, and lying on the first and last row and column
+		framebuffer.draw_line(&vec2f{x: -50.0f32, y: -10.0f32}, &vec2f{x: -5.0f32, y: 200.0f32}, colors::WHITE as u8);
+		framebuffer.draw_line(&vec2f{x: 0.0f32, y: 0.0f32}, &vec2f{x: 0.0f32, y: 1000.0f32}, colors::BLUE as u8);
+		framebuffer.draw_line(&vec2f{x: -1000.0f32, y: 159.0f32}, &vec2f{x: 1000.0f32, y: 159.0f32}, colors::BLUE as u8);
+	});
+}
+
+#[test]
+fn lines_styled()
+{
+	assert_golden("lines_styled", |framebuffer, _| {
+		let dashed = LineStyle::solid().with_dash(0b0000_1111, 8);
+		let dotted = LineStyle::solid().with_dash(0b01, 2);
+		let thick = LineStyle::solid().with_thickness(4);
+		let thick_dashed = thick.with_dash(0b0011_1111, 10);
+
+		framebuffer.draw_line_styled(&vec2f{x: 5.0f32, y: 10.0f32}, &vec2f{x: 115.0f32, y: 20.0f32}, &dashed, colors::WHITE as u8);
+		framebuffer.draw_line_styled(&vec2f{x: 5.0f32, y: 30.0f32}, &vec2f{x: 115.0f32, y: 60.0f32}, &dotted, colors::RED as u8);
+		framebuffer.draw_line_styled(&vec2f{x: 10.0f32, y: 70.0f32}, &vec2f{x: 40.0f32, y: 150.0f32}, &thick, colors::GREEN as u8);
+		framebuffer.draw_line_styled(&vec2f{x: 110.0f32, y: 70.0f32}, &vec2f{x: 60.0f32, y: 150.0f32}, &thick_dashed, colors::BLUE as u8);
+
+		// the dashes of a clipped line stay where they'd be on the whole line
+		framebuffer.draw_line_styled(&vec2f{x: -37.0f32, y: 158.0f32}, &vec2f{x: 150.0f32, y: 158.0f32}, &dashed, colors::WHITE as u8);
+		framebuffer.draw_line_styled(&vec2f{x: 60.0f32, y: -3.0f32}, &vec2f{x: 60.0f32, y: 5.0f32}, &thick, colors::WHITE as u8);
+	});
+}
+
+#[test]
+fn lines_anti_aliased()
+{
+	assert_golden("lines_anti_aliased", |framebuffer, _| {
+		let smooth = LineStyle::solid().with_anti_aliasing(true);
+		let center = vec2f{x: 60.5f32, y: 50.5f32};
+
+		for i in 0..16
+		{
+			let end = vec2f{x: 40.0f32, y: 0.0f32}.rotate(i as f32 * core::f32::consts::PI / 8.0f32 + 0.1f32).translate(center);
+			framebuffer.draw_line_styled(&center, &end, &smooth, colors::WHITE as u8);
+		}
+
+		// blended over a colored background, thick and dashed
+		framebuffer.sub_surface(0, 100, WIDTH, 60).fill(colors::BLUE as u8);
+		framebuffer.draw_line_styled(&vec2f{x: 5.0f32, y: 105.0f32}, &vec2f{x: 115.0f32, y: 125.0f32}, &smooth.with_thickness(3), colors::RED as u8);
+		framebuffer.draw_line_styled(&vec2f{x: 5.0f32, y: 150.0f32}, &vec2f{x: 115.0f32, y: 130.0f32}, &smooth.with_dash(0b0000_1111, 8), colors::WHITE as u8);
+
+		// running off the surface
+		framebuffer.draw_line_styled(&vec2f{x: -30.0f32, y: -20.0f32}, &vec2f{x: 150.0f32, y: 200.0f32}, &smooth, colors::GREEN as u8);
+	});
+}
+
+#[test]
+fn text_anti_aliased()
+{
+	assert_golden("text_anti_aliased", |framebuffer, _| {
+		let smooth = LineStyle::solid().with_anti_aliasing(true);
+
+		print_text_styled(framebuffer, b"3D CUBE", vec2f{x: 4.0f32, y: 20.0f32}, 15.0f32, &smooth, colors::WHITE as u8);
+		print_text_styled(framebuffer, b"SMALL", vec2f{x: 4.0f32, y: 40.0f32}, 8.0f32, &smooth, colors::WHITE as u8);
+		print_text_styled(framebuffer, b"SMALL", vec2f{x: 4.0f32, y: 55.0f32}, 8.0f32, &LineStyle::solid(), colors::WHITE as u8);
+		print_text_styled(framebuffer, b"BOLD", vec2f{x: 4.0f32, y: 90.0f32}, 20.0f32, &smooth.with_thickness(2), colors::GREEN as u8);
+	});
+}
+
+#[test]
+fn shapes()
+{
+	assert_golden("shapes", |framebuffer, _| {
+		framebuffer.fill_rect(4, 4, 30, 20, colors::RED as u8);
+		framebuffer.draw_rect(40, 4, 30, 20, colors::WHITE as u8);
+		framebuffer.fill_rounded_rect(76, 4, 40, 20, 6, colors::GREEN as u8);
+		framebuffer.draw_rounded_rect(76, 4, 40, 20, 6, colors::WHITE as u8);
+
+		framebuffer.fill_circle(20, 45, 15, colors::BLUE as u8);
+		framebuffer.draw_circle(20, 45, 15, colors::WHITE as u8);
+		framebuffer.draw_circle(20, 45, 3, colors::WHITE as u8);
+		framebuffer.draw_circle(20, 45, 0, colors::RED as u8);
+		framebuffer.fill_ellipse(60, 45, 18, 8, colors::RED as u8);
+		framebuffer.draw_ellipse(100, 45, 8, 18, colors::GREEN as u8);
+
+		// a gauge: three quarters of a ring with a filled slice
+		framebuffer.fill_arc(30, 95, 22, 0.75f32 * core::f32::consts::PI, 1.6f32 * core::f32::consts::PI, colors::GREEN as u8);
+		framebuffer.draw_arc(30, 95, 24, 0.75f32 * core::f32::consts::PI, 2.25f32 * core::f32::consts::PI, colors::WHITE as u8);
+		framebuffer.draw_arc(85, 95, 20, -0.5f32, 0.5f32, colors::RED as u8);
+		framebuffer.fill_arc(85, 95, 20, 2.0f32, 4.0f32, colors::BLUE as u8);
+
+		// hanging over the edges
+		framebuffer.fill_circle(0, 159, 12, colors::WHITE as u8);
+		framebuffer.draw_rounded_rect(100, 140, 40, 40, 10, colors::WHITE as u8);
+		framebuffer.fill_rect(-10, 125, 20, 8, colors::RED as u8);
+	});
+}
+
+#[test]
+fn polygons()
+{
+	assert_golden("polygons", |framebuffer, _| {
+		// a pentagram, the middle is only filled with the non-zero rule
+		let mut star = [vec2f::new(); 5];
+		for (i, point) in star.iter_mut().enumerate()
+		{
+			let angle = i as f32 * 4.0f32 * core::f32::consts::PI / 5.0f32 - core::f32::consts::PI / 2.0f32;
+			*point = vec2f{x: 28.0f32, y: 0.0f32}.rotate(angle);
+		}
+
+		let left_star = star.map(|point| point.translate(vec2f{x: 30.0f32, y: 35.0f32}));
+		let right_star = star.map(|point| point.translate(vec2f{x: 90.0f32, y: 35.0f32}));
+		framebuffer.fill_polygon(&left_star, FillRule::EvenOdd, colors::RED as u8);
+		framebuffer.fill_polygon(&right_star, FillRule::NonZero, colors::GREEN as u8);
+
+		// concave arrow with its outline on top
+		let arrow = [
+			vec2f{x: 10.0f32, y: 80.0f32},
+			vec2f{x: 60.0f32, y: 80.0f32},
+			vec2f{x: 60.0f32, y: 70.0f32},
+			vec2f{x: 90.0f32, y: 95.0f32},
+			vec2f{x: 60.0f32, y: 120.0f32},
+			vec2f{x: 60.0f32, y: 110.0f32},
+			vec2f{x: 10.0f32, y: 110.0f32},
+			vec2f{x: 25.0f32, y: 95.0f32}
+		];
+		framebuffer.fill_polygon(&arrow, FillRule::EvenOdd, colors::BLUE as u8);
+		framebuffer.draw_polygon(&arrow, colors::WHITE as u8);
+
+		// partly off the surface
+		let corner = [
+			vec2f{x: 100.0f32, y: 120.0f32},
+			vec2f{x: 150.0f32, y: 130.0f32},
+			vec2f{x: 130.0f32, y: 190.0f32},
+			vec2f{x: 80.0f32, y: 170.0f32}
+		];
+		framebuffer.fill_polygon(&corner, FillRule::NonZero, colors::WHITE as u8);
+	});
+}
+
+// embedded-graphics drawing on top of a rendered frame, through the DrawTarget impl
+#[test]
+fn embedded_graphics_overlay()
+{
+	assert_golden("embedded_graphics_overlay", |framebuffer, depthbuffer| {
+		render_scene(framebuffer, depthbuffer, RenderMode::Flat);
+
+		let panel = PrimitiveStyleBuilder::new()
+			.stroke_color(Rgb332::WHITE)
+			.stroke_width(1)
+			.fill_color(Rgb332::new(0, 0, 1))
+			.build();
+		RoundedRectangle::with_equal_corners(Rectangle::new(Point::new(4, 118), Size::new(112, 38)), Size::new(6, 6))
+			.into_styled(panel)
+			.draw(framebuffer)
+			.unwrap();
+
+		let text_style = MonoTextStyle::new(&FONT_6X10, Rgb332::YELLOW);
+		Text::new("FPS: 30", Point::new(10, 132), text_style).draw(framebuffer).unwrap();
+		Text::new("mode: flat", Point::new(10, 146), MonoTextStyle::new(&FONT_6X10, Rgb332::from(Rgb888::new(255, 128, 0)))).draw(framebuffer).unwrap();
+
+		Circle::new(Point::new(92, 124), 16)
+			.into_styled(PrimitiveStyleBuilder::new().fill_color(Rgb332::GREEN).build())
+			.draw(framebuffer)
+			.unwrap();
+		Triangle::new(Point::new(110, -5), Point::new(125, 20), Point::new(95, 20))
+			.into_styled(PrimitiveStyleBuilder::new().stroke_color(Rgb332::RED).stroke_width(2).build())
+			.draw(framebuffer)
+			.unwrap();
+
+		// 8x8 1bpp image converted on the fly, half of it hangs off the left edge
+		let arrow_data: [u8; 8] = [0x18, 0x3c, 0x7e, 0xff, 0x18, 0x18, 0x18, 0x18];
+		let arrow = ImageRaw::<BinaryColor>::new(&arrow_data, 8);
+		RawImage::new(&arrow, Point::new(-4, 100)).draw(&mut framebuffer.color_converted()).unwrap();
+		RawImage::new(&arrow, Point::new(20, 100)).draw(&mut framebuffer.color_converted()).unwrap();
+
+		// raw RGB 332 images are drawn as they are
+		let stripes: [u8; 12] = [0xe0, 0xe0, 0x1c, 0x1c, 0x03, 0x03, 0xe0, 0xe0, 0x1c, 0x1c, 0x03, 0x03];
+		let stripes = ImageRaw::<Rgb332>::new(&stripes, 6);
+		RawImage::new(&stripes, Point::new(40, 104)).draw(framebuffer).unwrap();
+	});
+}
+
+// the first and last row and column have to be reachable
+#[test]
+fn pixels_at_edges()
+{
+	assert_golden("pixels_at_edges", |framebuffer, _| {
+		for x in 0..WIDTH
+		{
+			framebuffer.draw_pixel(x, 0, colors::RED as u8);
+			framebuffer.draw_pixel(x, HEIGHT - 1, colors::GREEN as u8);
+		}
+
+		for y in 0..HEIGHT
+		{
+			framebuffer.draw_pixel(0, y, colors::BLUE as u8);
+			framebuffer.draw_pixel(WIDTH - 1, y, colors::WHITE as u8);
+		}
+
+		// out of bounds pixels are dropped
+		framebuffer.draw_pixel(WIDTH, 10, colors::WHITE as u8);
+		framebuffer.draw_pixel(10, HEIGHT, colors::WHITE as u8);
+	});
+}
+
+#[test]
+fn triangles_at_edges()
+{
+	assert_golden("triangles_at_edges", |framebuffer, depthbuffer| {
+		let w = WIDTH as f32;
+		let h = HEIGHT as f32;
+
+		// one triangle hanging over every corner
+		triangle::from(&vec2f{x: -20.0f32, y: -10.0f32}, &vec2f{x: 30.0f32, y: 5.0f32}, &vec2f{x: 5.0f32, y: 40.0f32}).draw(framebuffer, colors::RED as u8);
+		triangle::from(&vec2f{x: w + 20.0f32, y: -10.0f32}, &vec2f{x: w - 30.0f32, y: 5.0f32}, &vec2f{x: w - 5.0f32, y: 40.0f32}).draw(framebuffer, colors::GREEN as u8);
+		triangle::from(&vec2f{x: -20.0f32, y: h + 10.0f32}, &vec2f{x: 30.0f32, y: h - 5.0f32}, &vec2f{x: 5.0f32, y: h - 40.0f32}).draw(framebuffer, colors::BLUE as u8);
+		triangle::from(&vec2f{x: w + 20.0f32, y: h + 10.0f32}, &vec2f{x: w - 30.0f32, y: h - 5.0f32}, &vec2f{x: w - 5.0f32, y: h - 40.0f32}).draw(framebuffer, colors::WHITE as u8);
+
+		// flat top and flat bottom triangles touching the side edges
+		triangle::from(&vec2f{x: 0.0f32, y: 60.0f32}, &vec2f{x: 40.0f32, y: 60.0f32}, &vec2f{x: 20.0f32, y: 90.0f32}).draw(framebuffer, colors::WHITE as u8);
+		triangle::from(&vec2f{x: w - 40.0f32, y: 90.0f32}, &vec2f{x: w, y: 90.0f32}, &vec2f{x: w - 20.0f32, y: 60.0f32}).draw(framebuffer, colors::WHITE as u8);
+
+		// two depth tested triangles piercing each other, both cut by the bottom edge
+		triangle3d::from(&vec3f{x: 30.0f32, y: 100.0f32, z: 0.2f32}, &vec3f{x: 90.0f32, y: 110.0f32, z: 0.8f32}, &vec3f{x: 50.0f32, y: h + 20.0f32, z: 0.5f32})
+			.draw(framebuffer, depthbuffer, colors::RED as u8);
+		triangle3d::from(&vec3f{x: 90.0f32, y: 100.0f32, z: 0.2f32}, &vec3f{x: 30.0f32, y: 110.0f32, z: 0.8f32}, &vec3f{x: 70.0f32, y: h + 20.0f32, z: 0.5f32})
+			.draw(framebuffer, depthbuffer, colors::BLUE as u8);
+	});
+}
+
+// the harness itself: files survive the round trip and a single changed pixel is caught
+#[test]
+fn harness_detects_changes()
+{
+	let mut image = Image::new(4, 3);
+	image.set_pixel(1, 2, [10, 20, 30]);
+
+	let decoded = ppm::decode(&ppm::encode(&image)).expect("encoded image should decode");
+	assert_eq!(decoded, image);
+	assert!(golden::compare(&image, &decoded).is_none());
+
+	let mut changed = image.clone();
+	changed.set_pixel(3, 0, [255, 255, 255]);
+
+	let mismatch = golden::compare(&image, &changed).expect("a changed pixel should be reported");
+	assert_eq!(mismatch.differing_pixels, 1);
+	assert_eq!(mismatch.diff.get_pixel(3, 0), [255, 0, 255]);
+}
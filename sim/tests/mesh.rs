@@ -0,0 +1,41 @@
+use picorender::model::{Mesh, MAX_MESH_VERTICES};
+use picorender::vector::vec3f;
+
+static VERTICES: [vec3f; MAX_MESH_VERTICES + 1] = [vec3f{x: 0.0f32, y: 0.0f32, z: 0.0f32}; MAX_MESH_VERTICES + 1];
+static TRIANGLE: [[u16; 3]; 1] = [[0, 1, 2]];
+static EDGE: [[u16; 2]; 1] = [[0, 3]];
+
+#[test]
+fn meshes_up_to_the_limit_are_accepted()
+{
+	let mesh = Mesh::new(&VERTICES[..MAX_MESH_VERTICES], &TRIANGLE, &EDGE);
+	assert_eq!(mesh.vertices.len(), MAX_MESH_VERTICES);
+}
+
+#[test]
+#[should_panic(expected = "at most MAX_MESH_VERTICES")]
+fn too_many_vertices_are_rejected()
+{
+	Mesh::new(&VERTICES, &TRIANGLE, &EDGE);
+}
+
+#[test]
+#[should_panic(expected = "point at one of its vertices")]
+fn triangle_indices_past_the_vertices_are_rejected()
+{
+	Mesh::new(&VERTICES[..2], &TRIANGLE, &[]);
+}
+
+#[test]
+#[should_panic(expected = "point at one of its vertices")]
+fn edge_indices_past_the_vertices_are_rejected()
+{
+	Mesh::new(&VERTICES[..3], &TRIANGLE, &EDGE);
+}
+
+#[test]
+#[should_panic(expected = "every vertex needs a color")]
+fn missing_vertex_colors_are_rejected()
+{
+	Mesh::new(&VERTICES[..3], &TRIANGLE, &[]).with_vertex_colors(&[1, 2]);
+}
@@ -0,0 +1,199 @@
+use std::collections::VecDeque;
+use picorender::pipeline::{Fifo, FrameCells, Message, Presenter, Renderer, SharedInput};
+use picorender::scene::Input;
+
+// one direction of the SIO FIFO, just as deep
+const FIFO_DEPTH: usize = 8;
+
+#[derive(Default)]
+struct MockFifo
+{
+	words: VecDeque<u32>
+}
+
+impl Fifo for MockFifo
+{
+	fn write(&mut self, value: u32)
+	{
+		assert!(self.words.len() < FIFO_DEPTH, "a write would have blocked forever");
+		self.words.push_back(value);
+	}
+
+	fn read(&mut self) -> Option<u32>
+	{
+		self.words.pop_front()
+	}
+}
+
+// renderer to presenter and back
+#[derive(Default)]
+struct Channels
+{
+	to_presenter: MockFifo,
+	to_renderer: MockFifo
+}
+
+#[test]
+fn messages_round_trip()
+{
+	for message in [Message::FrameReady(0), Message::FrameReady(7), Message::FrameFree(0), Message::FrameFree(3)]
+	{
+		assert_eq!(Message::decode(message.encode()), Some(message));
+	}
+
+	assert_ne!(Message::FrameReady(1).encode(), Message::FrameFree(1).encode());
+
+	// what starting core1 can leave behind
+	assert_eq!(Message::decode(0), None);
+	assert_eq!(Message::decode(1), None);
+	assert_eq!(Message::decode(0x2000_0400), None);
+}
+
+#[test]
+fn frames_change_hands()
+{
+	let mut channels = Channels::default();
+	let mut renderer = Renderer::<2>::new();
+	let mut presenter = Presenter::<2>::new();
+
+	assert_eq!(presenter.next_frame(&mut channels.to_presenter), None);
+
+	// the renderer starts with both frames and can get one ahead of the display
+	assert_eq!(renderer.next_frame(&mut channels.to_renderer), Some(0));
+	renderer.submit(&mut channels.to_presenter, 0);
+	assert_eq!(renderer.next_frame(&mut channels.to_renderer), Some(1));
+	renderer.submit(&mut channels.to_presenter, 1);
+	assert_eq!(renderer.next_frame(&mut channels.to_renderer), None);
+
+	// one frame at a time, in order
+	assert_eq!(presenter.next_frame(&mut channels.to_presenter), Some(0));
+	assert_eq!(presenter.next_frame(&mut channels.to_presenter), None);
+	assert_eq!(presenter.sending(), Some(0));
+
+	presenter.frame_sent(&mut channels.to_renderer);
+	assert_eq!(presenter.sending(), None);
+	assert_eq!(presenter.next_frame(&mut channels.to_presenter), Some(1));
+
+	// the finished frame is back with the renderer
+	assert_eq!(renderer.next_frame(&mut channels.to_renderer), Some(0));
+	assert!(renderer.owns(0));
+	assert!(!renderer.owns(1));
+
+	// submitting a frame the renderer doesn't have does nothing
+	renderer.submit(&mut channels.to_presenter, 1);
+	assert!(channels.to_presenter.words.is_empty());
+}
+
+#[test]
+fn stray_words_are_ignored()
+{
+	let mut fifo = MockFifo::default();
+	let mut presenter = Presenter::<2>::new();
+
+	fifo.write(0);
+	fifo.write(Message::FrameFree(0).encode());
+	// out of range
+	fifo.write(Message::FrameReady(5).encode());
+	fifo.write(Message::FrameReady(1).encode());
+
+	assert_eq!(presenter.next_frame(&mut fifo), Some(1));
+
+	let mut renderer = Renderer::<2>::new();
+	renderer.submit(&mut MockFifo::default(), 0);
+	renderer.submit(&mut MockFifo::default(), 1);
+
+	fifo.write(Message::FrameReady(0).encode());
+	fifo.write(Message::FrameFree(9).encode());
+	assert_eq!(renderer.next_frame(&mut fifo), None);
+}
+
+// A small deterministic pseudo random number generator, so the two sides take turns in
+// an irregular order without the test being flaky.
+struct Lcg(u64);
+
+impl Lcg
+{
+	fn next(&mut self) -> u64
+	{
+		self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+		self.0 >> 33
+	}
+}
+
+// Both sides run in random steps, like two cores running at their own pace. Every frame holds
+// the number of the last frame rendered into it, the presenter has to see 1, 2, 3, ... and a
+// frame must never be used by both sides at once.
+#[test]
+fn interleaved_cores_present_every_frame_in_order()
+{
+	const FRAMES: usize = 3;
+
+	let cells = FrameCells::new([0u32; FRAMES]);
+	let mut channels = Channels::default();
+	let mut renderer = Renderer::<FRAMES>::new();
+	let mut presenter = Presenter::<FRAMES>::new();
+	let mut random = Lcg(0x1234_5678);
+
+	let mut rendered = 0u32;
+	let mut presented = Vec::new();
+	// a transfer takes a few steps
+	let mut transfer_steps = 0;
+
+	while presented.len() < 200
+	{
+		if random.next() & 1 == 0
+		{
+			// renderer core
+			if let Some(index) = renderer.next_frame(&mut channels.to_renderer)
+			{
+				assert_ne!(presenter.sending(), Some(index), "frame {} is on both cores", index);
+
+				rendered += 1;
+				*unsafe { cells.get(index) } = rendered;
+				renderer.submit(&mut channels.to_presenter, index);
+			}
+		}
+		else
+		{
+			// presenter core
+			match presenter.sending()
+			{
+				Some(index) =>
+				{
+					assert!(!renderer.owns(index), "frame {} is on both cores", index);
+
+					transfer_steps += 1;
+					if transfer_steps == 3
+					{
+						presented.push(*unsafe { cells.get(index) });
+						transfer_steps = 0;
+						presenter.frame_sent(&mut channels.to_renderer);
+					}
+				},
+				None =>
+				{
+					presenter.next_frame(&mut channels.to_presenter);
+				}
+			}
+		}
+	}
+
+	let expected: Vec<u32> = (1..=presented.len() as u32).collect();
+	assert_eq!(presented, expected);
+}
+
+#[test]
+fn shared_input_keeps_every_button()
+{
+	let shared = SharedInput::new();
+	assert!(!shared.load().a);
+
+	for buttons in 0..16u8
+	{
+		let input = Input{a: buttons & 1 != 0, b: buttons & 2 != 0, x: buttons & 4 != 0, y: buttons & 8 != 0};
+		shared.store(&input);
+
+		let loaded = shared.load();
+		assert_eq!((loaded.a, loaded.b, loaded.x, loaded.y), (input.a, input.b, input.x, input.y));
+	}
+}
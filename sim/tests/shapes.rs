@@ -0,0 +1,81 @@
+use picorender::framebuffer::Surface;
+use picorender::shapes::{FillRule, MAX_ROW_CROSSINGS};
+use picorender::vector::vec2f;
+
+// a regular polygon around the middle of a 64x64 surface
+fn polygon(points: usize) -> Vec<vec2f>
+{
+	(0..points).map(|i| {
+		let angle = i as f32 / points as f32 * core::f32::consts::TAU;
+		vec2f{x: 32.0f32 + 20.0f32 * angle.cos(), y: 32.0f32 + 20.0f32 * angle.sin()}
+	}).collect()
+}
+
+// pixels whose centers are inside the polygon's inscribed circle are filled, the ones outside
+// its circumscribed circle aren't, give or take half a pixel
+fn check_polygon(data: &[u8], points: usize)
+{
+	let inner = 20.0f32 * (core::f32::consts::PI / points as f32).cos() - 0.5f32;
+
+	for y in 0..64
+	{
+		for x in 0..64
+		{
+			let distance = f32::hypot(x as f32 + 0.5f32 - 32.0f32, y as f32 + 0.5f32 - 32.0f32);
+
+			if distance < inner
+			{
+				assert_eq!(data[y * 64 + x], 1, "({}, {}) is inside", x, y);
+			}
+			else if distance > 20.5f32
+			{
+				assert_eq!(data[y * 64 + x], 0, "({}, {}) is outside", x, y);
+			}
+		}
+	}
+}
+
+#[test]
+fn polygons_with_many_points_are_filled_whole()
+{
+	for points in [8, 64, 65, 500]
+	{
+		let mut data = vec![0u8; 64 * 64];
+		let mut surface = Surface::new(&mut data, 64, 64);
+
+		surface.fill_polygon(&polygon(points), FillRule::NonZero, 1);
+		check_polygon(&data, points);
+	}
+}
+
+#[test]
+fn rows_with_too_many_crossings_keep_the_leftmost_spans()
+{
+	// a comb with one tooth every 2 pixels, each row below the spine crosses 2 edges per tooth
+	let teeth = MAX_ROW_CROSSINGS;
+	let mut comb = vec![vec2f{x: 0.0f32, y: 0.0f32}];
+	for tooth in 0..teeth
+	{
+		let x = tooth as f32 * 2.0f32;
+		comb.push(vec2f{x, y: 10.0f32});
+		comb.push(vec2f{x: x + 1.0f32, y: 10.0f32});
+		comb.push(vec2f{x: x + 1.0f32, y: 2.0f32});
+		comb.push(vec2f{x: x + 2.0f32, y: 2.0f32});
+	}
+	comb.push(vec2f{x: teeth as f32 * 2.0f32, y: 0.0f32});
+
+	let width = teeth * 2 + 2;
+	let mut data = vec![0u8; width * 12];
+	let mut surface = Surface::new(&mut data, width, 12);
+	surface.fill_polygon(&comb, FillRule::EvenOdd, 1);
+
+	// the spine is whole, below it only the teeth of the first MAX_ROW_CROSSINGS crossings
+	assert!(data[..teeth * 2].iter().all(|&pixel| pixel == 1));
+	let row = &data[5 * width..6 * width];
+	for tooth in 0..teeth
+	{
+		let expected = if tooth < MAX_ROW_CROSSINGS / 2 { 1 } else { 0 };
+		assert_eq!(row[tooth * 2], expected, "tooth {}", tooth);
+		assert_eq!(row[tooth * 2 + 1], 0);
+	}
+}
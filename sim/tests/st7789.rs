@@ -0,0 +1,267 @@
+use std::cell::Cell;
+use std::convert::Infallible;
+use display_interface::{DataFormat, DisplayError, WriteOnlyDataCommand};
+use embedded_hal::blocking::delay::DelayUs;
+use embedded_hal::digital::v2::{InputPin, OutputPin};
+use picorender::st7789::{self, Instruction, Orientation, St7789, TearingEffect};
+
+// what went over the bus, data sent right after a command is its parameters
+#[derive(Debug, PartialEq)]
+enum Sent
+{
+	Command(u8),
+	Data(Vec<u8>)
+}
+
+#[derive(Default)]
+struct MockInterface
+{
+	sent: Vec<Sent>
+}
+
+fn bytes(format: DataFormat) -> Vec<u8>
+{
+	match format
+	{
+		DataFormat::U8Iter(iter) => iter.collect(),
+		DataFormat::U16BEIter(iter) => iter.flat_map(|value: u16| value.to_be_bytes()).collect(),
+		_ => panic!("the driver only sends iterators")
+	}
+}
+
+impl WriteOnlyDataCommand for MockInterface
+{
+	fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError>
+	{
+		for command in bytes(cmd)
+		{
+			self.sent.push(Sent::Command(command));
+		}
+
+		Ok(())
+	}
+
+	fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError>
+	{
+		self.sent.push(Sent::Data(bytes(buf)));
+		Ok(())
+	}
+}
+
+#[derive(Default)]
+struct MockDelay
+{
+	us: u32
+}
+
+impl DelayUs<u32> for MockDelay
+{
+	fn delay_us(&mut self, us: u32)
+	{
+		self.us += us;
+	}
+}
+
+#[derive(Default)]
+struct MockPin
+{
+	levels: Vec<bool>
+}
+
+impl OutputPin for MockPin
+{
+	type Error = Infallible;
+
+	fn set_low(&mut self) -> Result<(), Infallible>
+	{
+		self.levels.push(false);
+		Ok(())
+	}
+
+	fn set_high(&mut self) -> Result<(), Infallible>
+	{
+		self.levels.push(true);
+		Ok(())
+	}
+}
+
+// the commands with their parameters, the empty data that switches to pixel data is dropped
+fn commands(display: St7789<MockInterface>) -> Vec<(u8, Vec<u8>)>
+{
+	let mut commands: Vec<(u8, Vec<u8>)> = Vec::new();
+
+	for sent in display.release().sent
+	{
+		match sent
+		{
+			Sent::Command(command) => commands.push((command, Vec::new())),
+			Sent::Data(data) => commands.last_mut().expect("data before any command").1.extend(data)
+		}
+	}
+
+	commands
+}
+
+fn command(instruction: Instruction, parameters: &[u8]) -> (u8, Vec<u8>)
+{
+	(instruction as u8, parameters.to_vec())
+}
+
+#[test]
+fn init_sends_the_power_on_sequence()
+{
+	let mut display = St7789::new(MockInterface::default());
+	let mut delay = MockDelay::default();
+	let mut reset_pin = MockPin::default();
+
+	display.init(&mut delay, &mut reset_pin).unwrap();
+
+	assert_eq!(reset_pin.levels, [true, false, true]);
+	// the reset and sleep out waits at least
+	assert!(delay.us >= 150_000 + 120_000);
+	assert_eq!(commands(display), [
+		command(Instruction::SWRESET, &[]),
+		command(Instruction::SLPOUT, &[]),
+		// 0 fixed rows on top, 320 scrolling, 0 fixed at the bottom
+		command(Instruction::VSCRDEF, &[0x00, 0x00, 0x01, 0x40, 0x00, 0x00]),
+		command(Instruction::VSCSAD, &[0x00, 0x00]),
+		command(Instruction::MADCTL, &[0x00]),
+		command(Instruction::COLMOD, &[0x55]),
+		command(Instruction::INVON, &[]),
+		command(Instruction::NORON, &[]),
+		command(Instruction::DISPON, &[])
+	]);
+}
+
+#[test]
+fn orientations_set_madctl()
+{
+	let expected = [
+		(0, false, 0x00), (90, false, 0x60), (180, false, 0xC0), (270, false, 0xA0),
+		(0, true, 0x40), (90, true, 0xE0), (180, true, 0x80), (270, true, 0x20)
+	];
+
+	for (degrees, mirrored, madctl) in expected
+	{
+		let mut display = St7789::new(MockInterface::default());
+		display.set_orientation(Orientation::from_degrees(degrees).unwrap(), mirrored).unwrap();
+
+		assert_eq!(commands(display), [command(Instruction::MADCTL, &[madctl])], "{} degrees, mirrored {}", degrees, mirrored);
+	}
+
+	assert_eq!(Orientation::from_degrees(45), None);
+}
+
+#[test]
+fn windows_follow_the_orientation()
+{
+	let mut display = St7789::new(MockInterface::default());
+	assert_eq!(display.size(), (240, 320));
+	assert!(matches!(display.set_address_window(0, 0, 319, 239), Err(DisplayError::OutOfBoundsError)));
+
+	display.set_orientation(Orientation::Landscape, false).unwrap();
+	assert_eq!(display.size(), (320, 240));
+	display.write_pixels(300, 10, 301, 10, [0xF800u16, 0x001F]).unwrap();
+	assert!(matches!(display.set_address_window(0, 0, 239, 319), Err(DisplayError::OutOfBoundsError)));
+	assert!(matches!(display.set_address_window(5, 0, 4, 0), Err(DisplayError::OutOfBoundsError)));
+
+	assert_eq!(commands(display), [
+		command(Instruction::MADCTL, &[0x60]),
+		command(Instruction::CASET, &[0x01, 0x2C, 0x01, 0x2D]),
+		command(Instruction::RASET, &[0x00, 0x0A, 0x00, 0x0A]),
+		command(Instruction::RAMWR, &[0xF8, 0x00, 0x00, 0x1F])
+	]);
+}
+
+#[test]
+fn scrolling_wraps_inside_the_scroll_area()
+{
+	let mut display = St7789::new(MockInterface::default());
+
+	// a 20 row header and a 40 row footer stay in place
+	display.set_scroll_area(20, 40).unwrap();
+	display.set_scroll_offset(100).unwrap();
+	display.set_scroll_offset(260 + 5).unwrap();
+	assert!(matches!(display.set_scroll_area(200, 120), Err(DisplayError::OutOfBoundsError)));
+
+	assert_eq!(commands(display), [
+		command(Instruction::VSCRDEF, &[0x00, 0x14, 0x01, 0x04, 0x00, 0x28]),
+		command(Instruction::VSCSAD, &[0x00, 0x14]),
+		command(Instruction::VSCSAD, &[0x00, 0x78]),
+		command(Instruction::VSCSAD, &[0x00, 0x19])
+	]);
+}
+
+#[test]
+fn power_and_panel_modes()
+{
+	let mut display = St7789::new(MockInterface::default());
+	let mut delay = MockDelay::default();
+
+	display.set_partial_area(100, 299).unwrap();
+	display.set_normal_mode().unwrap();
+	assert!(matches!(display.set_partial_area(0, 320), Err(DisplayError::OutOfBoundsError)));
+
+	display.sleep(&mut delay).unwrap();
+	display.wake(&mut delay).unwrap();
+	assert!(delay.us >= 5_000 + 120_000);
+
+	display.set_inverted(false).unwrap();
+	display.set_inverted(true).unwrap();
+	display.set_brightness(0x80).unwrap();
+
+	display.set_tearing_effect(Some(TearingEffect::VerticalBlank)).unwrap();
+	display.set_tearing_effect(Some(TearingEffect::HorizontalAndVerticalBlank)).unwrap();
+	display.set_tear_scanline(300).unwrap();
+	display.set_tearing_effect(None).unwrap();
+
+	assert_eq!(commands(display), [
+		command(Instruction::PTLAR, &[0x00, 0x64, 0x01, 0x2B]),
+		command(Instruction::PTLON, &[]),
+		command(Instruction::NORON, &[]),
+		command(Instruction::SLPIN, &[]),
+		command(Instruction::SLPOUT, &[]),
+		command(Instruction::INVOFF, &[]),
+		command(Instruction::INVON, &[]),
+		command(Instruction::WRCTRLD, &[0x24]),
+		command(Instruction::WRDISBV, &[0x80]),
+		command(Instruction::TEON, &[0x00]),
+		command(Instruction::TEON, &[0x01]),
+		command(Instruction::STE, &[0x01, 0x2C]),
+		command(Instruction::TEOFF, &[])
+	]);
+}
+
+// a TE line that reads as the given levels, one per read
+struct MockTearingLine
+{
+	levels: Vec<bool>,
+	reads: Cell<usize>
+}
+
+impl InputPin for MockTearingLine
+{
+	type Error = Infallible;
+
+	fn is_high(&self) -> Result<bool, Infallible>
+	{
+		let level = self.levels[self.reads.get()];
+		self.reads.set(self.reads.get() + 1);
+		Ok(level)
+	}
+
+	fn is_low(&self) -> Result<bool, Infallible>
+	{
+		self.is_high().map(|high| !high)
+	}
+}
+
+#[test]
+fn waits_for_the_rising_edge_of_the_tearing_line()
+{
+	// halfway through a blanking period, that one is too late already
+	let te = MockTearingLine{levels: vec![true, true, false, false, false, true, true], reads: Cell::new(0)};
+
+	st7789::wait_for_tearing_effect(&te).unwrap();
+	assert_eq!(te.reads.get(), 6);
+}
@@ -0,0 +1,60 @@
+use picorender::vector::vec3f;
+use picorender::model::{triangle3d, TriangleSink};
+use picorender::tiles::{BinnedTriangle, TriangleBins};
+
+fn flat(top: f32, bottom: f32) -> triangle3d
+{
+	triangle3d::from(&vec3f{x: 10.0f32, y: top, z: 0.5f32}, &vec3f{x: 50.0f32, y: bottom, z: 0.5f32}, &vec3f{x: 30.0f32, y: top, z: 0.5f32})
+}
+
+fn strip_colors(bins: &TriangleBins<4>, strip: usize) -> Vec<u8>
+{
+	bins.strip_triangles(strip).map(|triangle| match triangle
+	{
+		BinnedTriangle::Flat{color, ..} => *color,
+		BinnedTriangle::Shaded{..} => panic!("only flat triangles were added")
+	}).collect()
+}
+
+#[test]
+fn triangles_go_into_the_strips_they_cover()
+{
+	// 100 rows in strips of 32, the last one only has 4 rows
+	let mut bins = TriangleBins::<4>::new(100, 32);
+	assert_eq!(bins.strip_count(), 4);
+	assert_eq!(bins.strip_rows(1), (32, 32));
+	assert_eq!(bins.strip_rows(3), (96, 4));
+
+	bins.flat(&flat(2.0f32, 20.0f32), 1);
+	// reaches a half pixel into the next strip, the span on row 32 is drawn
+	bins.flat(&flat(10.0f32, 32.5f32), 2);
+	bins.flat(&flat(40.0f32, 300.0f32), 3);
+	// off screen
+	bins.flat(&flat(-40.0f32, -2.0f32), 4);
+	bins.flat(&flat(100.0f32, 120.0f32), 5);
+
+	assert_eq!(bins.len(), 3);
+	assert_eq!(strip_colors(&bins, 0), [1, 2]);
+	assert_eq!(strip_colors(&bins, 1), [2, 3]);
+	assert_eq!(strip_colors(&bins, 2), [3]);
+	assert_eq!(strip_colors(&bins, 3), [3]);
+}
+
+#[test]
+fn full_bins_count_what_they_drop()
+{
+	let mut bins = TriangleBins::<4>::new(100, 32);
+
+	for color in 0..6
+	{
+		bins.flat(&flat(0.0f32, 10.0f32), color);
+	}
+
+	assert_eq!(bins.len(), 4);
+	assert_eq!(bins.dropped(), 2);
+	assert_eq!(strip_colors(&bins, 0), [0, 1, 2, 3]);
+
+	bins.clear();
+	assert!(bins.is_empty());
+	assert_eq!(bins.dropped(), 0);
+}
@@ -0,0 +1,332 @@
+use picorender::transfer::{DisplayDma, FrameTransfer, TransferState, LINE_BYTES, expand_line};
+use picorender::pixel_ops::convert_pixel;
+use picorender::damage::{DamageList, DamageRect};
+use picorender::{SCREEN_WIDTH, SCREEN_HEIGHT};
+
+// Stands in for the RP2040 DMA channel: keeps a copy of every line it was given and where the
+// line buffer was, so the tests can check nothing writes to a line while it's being "sent".
+#[derive(Default)]
+struct MockDma
+{
+	windows: Vec<DamageRect>,
+	lines: Vec<Vec<u8>>,
+	in_flight: Option<(*const u8, usize)>,
+	// the display stops taking windows after this many
+	working_windows: Option<usize>
+}
+
+#[derive(Debug, PartialEq)]
+struct WindowRejected;
+
+impl DisplayDma for MockDma
+{
+	type Error = WindowRejected;
+
+	fn begin_window(&mut self, rect: &DamageRect) -> Result<(), WindowRejected>
+	{
+		assert!(self.in_flight.is_none(), "the address window changed while a line was still being sent");
+
+		if self.working_windows == Some(self.windows.len())
+		{
+			return Err(WindowRejected);
+		}
+
+		self.windows.push(*rect);
+		Ok(())
+	}
+
+	fn start_line(&mut self, data: &[u8])
+	{
+		assert!(self.in_flight.is_none(), "a line was started while the previous one was still being sent");
+
+		self.lines.push(data.to_vec());
+		self.in_flight = Some((data.as_ptr(), data.len()));
+	}
+}
+
+impl MockDma
+{
+	fn frames_begun(&self) -> usize
+	{
+		self.windows.iter().filter(|window| window.area() == SCREEN_WIDTH * SCREEN_HEIGHT).count()
+	}
+
+	// what the DMA interrupt does, after making sure the line it was sending is still intact
+	fn finish_line(transfer: &mut FrameTransfer<MockDma>) -> Result<(), WindowRejected>
+	{
+		if let Some((pointer, len)) = transfer.dma.in_flight.take()
+		{
+			let sent = unsafe { std::slice::from_raw_parts(pointer, len) };
+			assert_eq!(sent, transfer.dma.lines.last().unwrap().as_slice(), "the line buffer changed while it was being sent");
+		}
+
+		transfer.line_done()
+	}
+}
+
+fn new_frame(fill: impl Fn(usize, usize) -> u8) -> &'static mut [u8]
+{
+	let mut frame = vec![0u8; SCREEN_WIDTH * SCREEN_HEIGHT];
+
+	for y in 0..SCREEN_HEIGHT
+	{
+		for x in 0..SCREEN_WIDTH
+		{
+			frame[y * SCREEN_WIDTH + x] = fill(x, y);
+		}
+	}
+
+	Box::leak(frame.into_boxed_slice())
+}
+
+fn expected_line(frame: &[u8], y: usize) -> Vec<u8>
+{
+	let mut line = vec![0u8; LINE_BYTES];
+	expand_line(&frame[y * SCREEN_WIDTH..(y + 1) * SCREEN_WIDTH], &mut line);
+	line
+}
+
+fn run_to_completion(transfer: &mut FrameTransfer<MockDma>)
+{
+	let mut interrupts = 0;
+
+	while !transfer.is_complete()
+	{
+		MockDma::finish_line(transfer).unwrap();
+		interrupts += 1;
+		assert!(interrupts <= SCREEN_HEIGHT, "the transfer never completed");
+	}
+}
+
+#[test]
+fn expand_line_is_big_endian_rgb565()
+{
+	let mut out = [0u8; 6];
+	expand_line(&[0b1110_0000, 0b0001_1100, 0b0000_0011], &mut out);
+
+	assert_eq!(out[0..2], convert_pixel(0b1110_0000).to_be_bytes());
+	assert_eq!(out[2..4], convert_pixel(0b0001_1100).to_be_bytes());
+	assert_eq!(out[4..6], convert_pixel(0b0000_0011).to_be_bytes());
+	assert_eq!(out[0..2], [0xF8, 0x00]);
+}
+
+#[test]
+fn streams_every_line_in_order()
+{
+	let frame = new_frame(|x, y| (x * 7 + y * 13) as u8);
+	let frame_pointer = frame.as_ptr();
+	let reference = frame.to_vec();
+
+	let mut transfer = FrameTransfer::new(MockDma::default());
+	assert_eq!(transfer.state(), TransferState::Idle);
+
+	transfer.start(frame).unwrap();
+	assert_eq!(transfer.state(), TransferState::Sending{rect: 0, line: 0});
+	assert_eq!(transfer.dma.frames_begun(), 1);
+	assert!(transfer.take_finished().is_none());
+
+	run_to_completion(&mut transfer);
+
+	assert_eq!(transfer.dma.lines.len(), SCREEN_HEIGHT);
+	for (y, line) in transfer.dma.lines.iter().enumerate()
+	{
+		assert_eq!(*line, expected_line(&reference, y), "line {}", y);
+	}
+
+	// the same buffer comes back and the transfer can take the next frame
+	let returned = transfer.take_finished().unwrap();
+	assert_eq!(returned.as_ptr(), frame_pointer);
+	assert_eq!(transfer.state(), TransferState::Idle);
+	assert!(transfer.take_finished().is_none());
+}
+
+#[test]
+fn busy_transfer_rejects_frames()
+{
+	let mut transfer = FrameTransfer::new(MockDma::default());
+	transfer.start(new_frame(|_, _| 1u8)).unwrap();
+
+	let second = new_frame(|_, _| 2u8);
+	let second_pointer = second.as_ptr();
+	let second = transfer.start(second).unwrap_err();
+	assert_eq!(second.as_ptr(), second_pointer);
+
+	// still rejected after the last line, until the finished frame is taken back
+	run_to_completion(&mut transfer);
+	let second = transfer.start(second).unwrap_err();
+
+	transfer.take_finished().unwrap();
+	transfer.start(second).unwrap();
+	assert_eq!(transfer.dma.frames_begun(), 2);
+
+	// too small for the screen
+	let mut idle = FrameTransfer::new(MockDma::default());
+	assert!(idle.start(Box::leak(vec![0u8; 16].into_boxed_slice())).is_err());
+	assert_eq!(idle.dma.frames_begun(), 0);
+}
+
+#[test]
+fn interrupts_without_a_frame_are_ignored()
+{
+	let mut transfer = FrameTransfer::new(MockDma::default());
+	transfer.line_done().unwrap();
+	assert_eq!(transfer.state(), TransferState::Idle);
+
+	transfer.start(new_frame(|_, _| 3u8)).unwrap();
+	run_to_completion(&mut transfer);
+	transfer.dma.in_flight = None;
+	transfer.line_done().unwrap();
+	assert_eq!(transfer.state(), TransferState::Complete);
+	assert_eq!(transfer.dma.lines.len(), SCREEN_HEIGHT);
+}
+
+#[test]
+fn sends_only_the_damaged_regions()
+{
+	let frame = new_frame(|x, y| (x * 3 + y * 5) as u8);
+	let reference = frame.to_vec();
+
+	let mut damage = DamageList::new();
+	damage.add(DamageRect::new(10, 20, 30, 4).unwrap());
+	damage.add(DamageRect::new(80, 100, 5, 2).unwrap());
+	// reaches past the bottom right corner and gets cut off
+	damage.add(DamageRect::new(SCREEN_WIDTH - 2, SCREEN_HEIGHT - 1, 10, 10).unwrap());
+
+	let mut transfer = FrameTransfer::new(MockDma::default());
+	transfer.start_regions(frame, &damage).unwrap();
+	run_to_completion(&mut transfer);
+
+	assert_eq!(transfer.dma.windows, vec![
+		DamageRect{left: 10, top: 20, right: 39, bottom: 23},
+		DamageRect{left: 80, top: 100, right: 84, bottom: 101},
+		DamageRect{left: SCREEN_WIDTH - 2, top: SCREEN_HEIGHT - 1, right: SCREEN_WIDTH - 1, bottom: SCREEN_HEIGHT - 1}
+	]);
+
+	// every line holds just the part of the row inside its window
+	let mut lines = transfer.dma.lines.iter();
+	for window in &transfer.dma.windows
+	{
+		for y in window.top..=window.bottom
+		{
+			let full_line = expected_line(&reference, y);
+			assert_eq!(*lines.next().unwrap(), full_line[window.left * 2..(window.right + 1) * 2], "line {} of {:?}", y, window);
+		}
+	}
+	assert!(lines.next().is_none());
+
+	// nothing changed, nothing to send
+	let frame = transfer.take_finished().unwrap();
+	transfer.start_regions(frame, &DamageList::new()).unwrap();
+	assert!(transfer.is_complete());
+	assert_eq!(transfer.dma.windows.len(), 3);
+	assert!(transfer.take_finished().is_some());
+}
+
+#[test]
+fn sends_strips_to_their_rows()
+{
+	let rows = 16;
+	let strip: &'static mut [u8] = Box::leak(vec![0u8; SCREEN_WIDTH * rows].into_boxed_slice());
+	for (i, pix) in strip.iter_mut().enumerate()
+	{
+		*pix = (i / SCREEN_WIDTH) as u8;
+	}
+
+	let mut transfer = FrameTransfer::new(MockDma::default());
+	transfer.start_strip(strip, 32).unwrap();
+	assert_eq!(transfer.state(), TransferState::Sending{rect: 0, line: 32});
+	run_to_completion(&mut transfer);
+
+	assert_eq!(transfer.dma.windows, vec![DamageRect{left: 0, top: 32, right: SCREEN_WIDTH - 1, bottom: 32 + rows - 1}]);
+	assert_eq!(transfer.dma.lines.len(), rows);
+	for (row, line) in transfer.dma.lines.iter().enumerate()
+	{
+		assert_eq!(line.len(), LINE_BYTES);
+		assert_eq!(line[..2], convert_pixel(row as u8).to_be_bytes(), "row {}", row);
+	}
+
+	// past the bottom of the screen, or not made of whole rows
+	let strip = transfer.take_finished().unwrap();
+	let strip = transfer.start_strip(strip, SCREEN_HEIGHT - rows + 1).unwrap_err();
+	assert!(transfer.start_strip(Box::leak(vec![0u8; SCREEN_WIDTH + 1].into_boxed_slice()), 0).is_err());
+
+	// the last rows of the screen
+	transfer.start_strip(strip, SCREEN_HEIGHT - rows).unwrap();
+	run_to_completion(&mut transfer);
+	assert_eq!(transfer.dma.windows[1].bottom, SCREEN_HEIGHT - 1);
+}
+
+// the firmware's main loop: the frame being rendered and the frame being sent swap every time
+#[test]
+fn double_buffered_frames_alternate()
+{
+	let mut transfer = FrameTransfer::new(MockDma::default());
+	let mut back = new_frame(|_, _| 0u8);
+	let mut spare = Some(new_frame(|_, _| 0u8));
+	let mut buffers_used = Vec::new();
+
+	for frame_number in 1..=5u8
+	{
+		// render
+		back.fill(frame_number);
+		buffers_used.push(back.as_ptr());
+
+		// the first frame has a free buffer, after that it's the one that was just sent
+		let next = match spare.take()
+		{
+			Some(buffer) => buffer,
+			None =>
+			{
+				run_to_completion(&mut transfer);
+				transfer.take_finished().unwrap()
+			}
+		};
+
+		transfer.start(back).unwrap();
+		back = next;
+	}
+
+	run_to_completion(&mut transfer);
+
+	assert_eq!(transfer.dma.frames_begun(), 5);
+	for (frame, lines) in transfer.dma.lines.chunks(SCREEN_HEIGHT).enumerate()
+	{
+		let expected = convert_pixel(frame as u8 + 1).to_be_bytes();
+		assert!(lines.iter().all(|line| line[..2] == expected), "frame {}", frame);
+	}
+
+	// two buffers, used in turns
+	assert_ne!(buffers_used[0], buffers_used[1]);
+	assert!(buffers_used.iter().step_by(2).all(|pointer| *pointer == buffers_used[0]));
+	assert!(buffers_used.iter().skip(1).step_by(2).all(|pointer| *pointer == buffers_used[1]));
+}
+
+#[test]
+fn rejected_windows_stop_the_transfer()
+{
+	let mut damage = DamageList::new();
+	damage.add(DamageRect::new(0, 0, 8, 2).unwrap());
+	damage.add(DamageRect::new(100, 200, 8, 2).unwrap());
+
+	// the second window fails from the interrupt, after the first rectangle's lines
+	let mut transfer = FrameTransfer::new(MockDma{working_windows: Some(1), ..MockDma::default()});
+	transfer.start_regions(new_frame(|_, _| 1u8), &damage).unwrap();
+	MockDma::finish_line(&mut transfer).unwrap();
+	assert_eq!(MockDma::finish_line(&mut transfer), Err(WindowRejected));
+	assert_eq!(transfer.state(), TransferState::Failed);
+	assert_eq!(transfer.dma.lines.len(), 2);
+
+	// nothing more is sent, and the frame comes back
+	transfer.dma.in_flight = None;
+	transfer.line_done().unwrap();
+	assert_eq!(transfer.dma.lines.len(), 2);
+	assert!(transfer.take_finished().is_some());
+	assert_eq!(transfer.state(), TransferState::Idle);
+
+	// failing right away in start leaves the same state behind
+	let mut transfer = FrameTransfer::new(MockDma{working_windows: Some(0), ..MockDma::default()});
+	transfer.start(new_frame(|_, _| 1u8)).unwrap();
+	assert_eq!(transfer.state(), TransferState::Failed);
+	assert!(transfer.dma.lines.is_empty());
+	assert!(transfer.take_finished().is_some());
+}
@@ -0,0 +1,449 @@
+use crate::vector::vec2f;
+use crate::framebuffer::Surface;
+use crate::font::Font;
+
+// Fixed size bitmap fonts. Glyphs are stored one after another, row by row, 1 bit per pixel
+// with the leftmost pixel in the highest bit and every row padded to whole bytes.
+// The atlases below cover printable ASCII (32 - 126).
+
+#[derive(Clone, Copy)]
+pub struct BitmapFont
+{
+	pub data: &'static [u8],
+	pub glyph_width: usize,
+	pub glyph_height: usize,
+	// rows above the baseline
+	pub ascent: usize,
+	pub first_char: u8,
+	pub char_count: usize,
+	// every font pixel becomes a scale x scale block
+	pub scale: usize,
+	// the rest of the character cell is filled with this, None leaves it transparent
+	pub background: Option<u8>
+}
+
+impl BitmapFont
+{
+	pub const fn new(data: &'static [u8], glyph_width: usize, glyph_height: usize, ascent: usize) -> BitmapFont
+	{
+		BitmapFont{
+			data,
+			glyph_width,
+			glyph_height,
+			ascent,
+			first_char: b' ',
+			char_count: data.len() / (glyph_height * glyph_width.div_ceil(8)),
+			scale: 1,
+			background: None
+		}
+	}
+
+	pub fn with_first_char(&self, first_char: u8) -> BitmapFont
+	{
+		BitmapFont{
+			first_char,
+			..*self
+		}
+	}
+
+	pub fn with_scale(&self, scale: usize) -> BitmapFont
+	{
+		BitmapFont{
+			scale: usize::max(1, scale),
+			..*self
+		}
+	}
+
+	pub fn with_background(&self, background: Option<u8>) -> BitmapFont
+	{
+		BitmapFont{
+			background,
+			..*self
+		}
+	}
+
+	fn bytes_per_row(&self) -> usize
+	{
+		self.glyph_width.div_ceil(8)
+	}
+
+	// the rows of a glyph, None for characters the atlas doesn't have
+	fn glyph(&self, char: u8) -> Option<&'static [u8]>
+	{
+		let index = (char as usize).checked_sub(self.first_char as usize)?;
+
+		if index >= self.char_count
+		{
+			return None;
+		}
+
+		let size = self.glyph_height * self.bytes_per_row();
+		Some(&self.data[index * size..(index + 1) * size])
+	}
+}
+
+impl Font for BitmapFont
+{
+	fn advance(&self, _char: u8) -> f32
+	{
+		(self.glyph_width * self.scale) as f32
+	}
+
+	fn ascent(&self) -> f32
+	{
+		(self.ascent * self.scale) as f32
+	}
+
+	fn line_height(&self) -> f32
+	{
+		(self.glyph_height * self.scale) as f32
+	}
+
+	fn draw_char(&self, framebuffer: &mut Surface, char: u8, position: vec2f, color: u8)
+	{
+		let x = libm::floorf(position.x) as i32;
+		let y = libm::floorf(position.y) as i32;
+		let scale = self.scale as i32;
+
+		if let Some(background) = self.background
+		{
+			framebuffer.fill_rect(x, y, self.glyph_width as i32 * scale, self.glyph_height as i32 * scale, background);
+		}
+
+		let glyph = match self.glyph(char)
+		{
+			Some(glyph) => glyph,
+			None => return
+		};
+
+		let bytes_per_row = self.bytes_per_row();
+
+		for (row, bits) in glyph.chunks(bytes_per_row).enumerate()
+		{
+			let is_set = |column: usize| bits[column / 8] & (0x80 >> (column % 8)) != 0;
+			let row_y = y + row as i32 * scale;
+
+			// runs of set pixels become one rectangle each
+			let mut column = 0usize;
+			while column < self.glyph_width
+			{
+				if !is_set(column)
+				{
+					column += 1;
+					continue;
+				}
+
+				let run_start = column;
+				while column < self.glyph_width && is_set(column)
+				{
+					column += 1;
+				}
+
+				framebuffer.fill_rect(x + run_start as i32 * scale, row_y, (column - run_start) as i32 * scale, scale, color);
+			}
+		}
+	}
+}
+
+// X11 misc-fixed 5x7 (public domain), the cell includes the spacing
+pub static FONT_5X7: BitmapFont = BitmapFont::new(&FONT_5X7_DATA, 5, 7, 6);
+
+// font8x8 basic latin by Daniel Hepper (public domain)
+pub static FONT_8X8: BitmapFont = BitmapFont::new(&FONT_8X8_DATA, 8, 8, 7);
+
+// X11 misc-fixed 8x13 (public domain) with a row of padding above and two below
+pub static FONT_8X16: BitmapFont = BitmapFont::new(&FONT_8X16_DATA, 8, 16, 12);
+
+static FONT_5X7_DATA: [u8; 95 * 7] = [
+	0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //  
+	0x20, 0x20, 0x20, 0x20, 0x00, 0x20, 0x00, // !
+	0x50, 0x50, 0x50, 0x00, 0x00, 0x00, 0x00, // "
+	0x00, 0x50, 0xf8, 0x50, 0xf8, 0x50, 0x00, // #
+	0x00, 0x70, 0xa0, 0x70, 0x28, 0x70, 0x00, // $
+	0x80, 0x90, 0x20, 0x40, 0x90, 0x10, 0x00, // %
+	0x00, 0x40, 0xa0, 0x40, 0xa0, 0x50, 0x00, // &
+	0x20, 0x20, 0x20, 0x00, 0x00, 0x00, 0x00, // '
+	0x20, 0x40, 0x40, 0x40, 0x40, 0x20, 0x00, // (
+	0x40, 0x20, 0x20, 0x20, 0x20, 0x40, 0x00, // )
+	0x00, 0x50, 0x20, 0x70, 0x20, 0x50, 0x00, // *
+	0x00, 0x20, 0x20, 0xf8, 0x20, 0x20, 0x00, // +
+	0x00, 0x00, 0x00, 0x00, 0x30, 0x20, 0x40, // ,
+	0x00, 0x00, 0x00, 0xf0, 0x00, 0x00, 0x00, // -
+	0x00, 0x00, 0x00, 0x00, 0x60, 0x60, 0x00, // .
+	0x00, 0x10, 0x20, 0x40, 0x80, 0x00, 0x00, // /
+	0x20, 0x50, 0x50, 0x50, 0x50, 0x20, 0x00, // 0
+	0x20, 0x60, 0x20, 0x20, 0x20, 0x70, 0x00, // 1
+	0x60, 0x90, 0x10, 0x20, 0x40, 0xf0, 0x00, // 2
+	0xf0, 0x10, 0x60, 0x10, 0x90, 0x60, 0x00, // 3
+	0x20, 0x60, 0xa0, 0xf0, 0x20, 0x20, 0x00, // 4
+	0xf0, 0x80, 0xe0, 0x10, 0x90, 0x60, 0x00, // 5
+	0x60, 0x80, 0xe0, 0x90, 0x90, 0x60, 0x00, // 6
+	0xf0, 0x10, 0x20, 0x20, 0x40, 0x40, 0x00, // 7
+	0x60, 0x90, 0x60, 0x90, 0x90, 0x60, 0x00, // 8
+	0x60, 0x90, 0x90, 0x70, 0x10, 0x60, 0x00, // 9
+	0x00, 0x60, 0x60, 0x00, 0x60, 0x60, 0x00, // :
+	0x00, 0x60, 0x60, 0x00, 0x60, 0x40, 0x80, // ;
+	0x00, 0x10, 0x20, 0x40, 0x20, 0x10, 0x00, // <
+	0x00, 0x00, 0xf0, 0x00, 0xf0, 0x00, 0x00, // =
+	0x00, 0x40, 0x20, 0x10, 0x20, 0x40, 0x00, // >
+	0x20, 0x50, 0x10, 0x20, 0x00, 0x20, 0x00, // ?
+	0x60, 0x90, 0xb0, 0xb0, 0x80, 0x60, 0x00, // @
+	0x60, 0x90, 0x90, 0xf0, 0x90, 0x90, 0x00, // A
+	0xe0, 0x90, 0xe0, 0x90, 0x90, 0xe0, 0x00, // B
+	0x60, 0x90, 0x80, 0x80, 0x90, 0x60, 0x00, // C
+	0xe0, 0x90, 0x90, 0x90, 0x90, 0xe0, 0x00, // D
+	0xf0, 0x80, 0xe0, 0x80, 0x80, 0xf0, 0x00, // E
+	0xf0, 0x80, 0xe0, 0x80, 0x80, 0x80, 0x00, // F
+	0x60, 0x90, 0x80, 0xb0, 0x90, 0x70, 0x00, // G
+	0x90, 0x90, 0xf0, 0x90, 0x90, 0x90, 0x00, // H
+	0x70, 0x20, 0x20, 0x20, 0x20, 0x70, 0x00, // I
+	0x10, 0x10, 0x10, 0x10, 0x90, 0x60, 0x00, // J
+	0x90, 0xa0, 0xc0, 0xc0, 0xa0, 0x90, 0x00, // K
+	0x80, 0x80, 0x80, 0x80, 0x80, 0xf0, 0x00, // L
+	0x90, 0xf0, 0xf0, 0x90, 0x90, 0x90, 0x00, // M
+	0x90, 0xd0, 0xd0, 0xb0, 0xb0, 0x90, 0x00, // N
+	0x60, 0x90, 0x90, 0x90, 0x90, 0x60, 0x00, // O
+	0xe0, 0x90, 0x90, 0xe0, 0x80, 0x80, 0x00, // P
+	0x60, 0x90, 0x90, 0x90, 0xd0, 0x60, 0x10, // Q
+	0xe0, 0x90, 0x90, 0xe0, 0xa0, 0x90, 0x00, // R
+	0x60, 0x90, 0x40, 0x20, 0x90, 0x60, 0x00, // S
+	0x70, 0x20, 0x20, 0x20, 0x20, 0x20, 0x00, // T
+	0x90, 0x90, 0x90, 0x90, 0x90, 0x60, 0x00, // U
+	0x90, 0x90, 0x90, 0x90, 0x60, 0x60, 0x00, // V
+	0x90, 0x90, 0x90, 0xf0, 0xf0, 0x90, 0x00, // W
+	0x90, 0x90, 0x60, 0x60, 0x90, 0x90, 0x00, // X
+	0x50, 0x50, 0x50, 0x20, 0x20, 0x20, 0x00, // Y
+	0xf0, 0x10, 0x20, 0x40, 0x80, 0xf0, 0x00, // Z
+	0x70, 0x40, 0x40, 0x40, 0x40, 0x70, 0x00, // [
+	0x00, 0x80, 0x40, 0x20, 0x10, 0x00, 0x00, // backslash
+	0x70, 0x10, 0x10, 0x10, 0x10, 0x70, 0x00, // ]
+	0x20, 0x50, 0x00, 0x00, 0x00, 0x00, 0x00, // ^
+	0x00, 0x00, 0x00, 0x00, 0x00, 0xf0, 0x00, // _
+	0x40, 0x20, 0x00, 0x00, 0x00, 0x00, 0x00, // `
+	0x00, 0x00, 0x70, 0x90, 0xb0, 0x50, 0x00, // a
+	0x80, 0x80, 0xe0, 0x90, 0x90, 0xe0, 0x00, // b
+	0x00, 0x00, 0x60, 0x80, 0x80, 0x60, 0x00, // c
+	0x10, 0x10, 0x70, 0x90, 0x90, 0x70, 0x00, // d
+	0x00, 0x00, 0x60, 0xb0, 0xc0, 0x60, 0x00, // e
+	0x20, 0x50, 0x40, 0xe0, 0x40, 0x40, 0x00, // f
+	0x00, 0x00, 0x70, 0x90, 0x60, 0x80, 0x70, // g
+	0x80, 0x80, 0xe0, 0x90, 0x90, 0x90, 0x00, // h
+	0x20, 0x00, 0x60, 0x20, 0x20, 0x70, 0x00, // i
+	0x10, 0x00, 0x10, 0x10, 0x10, 0x50, 0x20, // j
+	0x80, 0x80, 0xa0, 0xc0, 0xa0, 0x90, 0x00, // k
+	0x60, 0x20, 0x20, 0x20, 0x20, 0x70, 0x00, // l
+	0x00, 0x00, 0xa0, 0xf0, 0x90, 0x90, 0x00, // m
+	0x00, 0x00, 0xe0, 0x90, 0x90, 0x90, 0x00, // n
+	0x00, 0x00, 0x60, 0x90, 0x90, 0x60, 0x00, // o
+	0x00, 0x00, 0xe0, 0x90, 0x90, 0xe0, 0x80, // p
+	0x00, 0x00, 0x70, 0x90, 0x90, 0x70, 0x10, // q
+	0x00, 0x00, 0xe0, 0x90, 0x80, 0x80, 0x00, // r
+	0x00, 0x00, 0x70, 0xc0, 0x30, 0xe0, 0x00, // s
+	0x40, 0x40, 0xe0, 0x40, 0x40, 0x30, 0x00, // t
+	0x00, 0x00, 0x90, 0x90, 0x90, 0x70, 0x00, // u
+	0x00, 0x00, 0x50, 0x50, 0x50, 0x20, 0x00, // v
+	0x00, 0x00, 0x90, 0x90, 0xf0, 0xf0, 0x00, // w
+	0x00, 0x00, 0x90, 0x60, 0x60, 0x90, 0x00, // x
+	0x00, 0x00, 0x90, 0x90, 0x50, 0x20, 0x40, // y
+	0x00, 0x00, 0xf0, 0x20, 0x40, 0xf0, 0x00, // z
+	0x10, 0x20, 0x60, 0x20, 0x20, 0x10, 0x00, // {
+	0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x00, // |
+	0x40, 0x20, 0x30, 0x20, 0x20, 0x40, 0x00, // }
+	0x50, 0xa0, 0x00, 0x00, 0x00, 0x00, 0x00, // ~
+];
+
+static FONT_8X8_DATA: [u8; 95 * 8] = [
+	0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //  
+	0x18, 0x3c, 0x3c, 0x18, 0x18, 0x00, 0x18, 0x00, // !
+	0x6c, 0x6c, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // "
+	0x6c, 0x6c, 0xfe, 0x6c, 0xfe, 0x6c, 0x6c, 0x00, // #
+	0x30, 0x7c, 0xc0, 0x78, 0x0c, 0xf8, 0x30, 0x00, // $
+	0x00, 0xc6, 0xcc, 0x18, 0x30, 0x66, 0xc6, 0x00, // %
+	0x38, 0x6c, 0x38, 0x76, 0xdc, 0xcc, 0x76, 0x00, // &
+	0x60, 0x60, 0xc0, 0x00, 0x00, 0x00, 0x00, 0x00, // '
+	0x18, 0x30, 0x60, 0x60, 0x60, 0x30, 0x18, 0x00, // (
+	0x60, 0x30, 0x18, 0x18, 0x18, 0x30, 0x60, 0x00, // )
+	0x00, 0x66, 0x3c, 0xff, 0x3c, 0x66, 0x00, 0x00, // *
+	0x00, 0x30, 0x30, 0xfc, 0x30, 0x30, 0x00, 0x00, // +
+	0x00, 0x00, 0x00, 0x00, 0x00, 0x30, 0x30, 0x60, // ,
+	0x00, 0x00, 0x00, 0xfc, 0x00, 0x00, 0x00, 0x00, // -
+	0x00, 0x00, 0x00, 0x00, 0x00, 0x30, 0x30, 0x00, // .
+	0x06, 0x0c, 0x18, 0x30, 0x60, 0xc0, 0x80, 0x00, // /
+	0x7c, 0xc6, 0xce, 0xde, 0xf6, 0xe6, 0x7c, 0x00, // 0
+	0x30, 0x70, 0x30, 0x30, 0x30, 0x30, 0xfc, 0x00, // 1
+	0x78, 0xcc, 0x0c, 0x38, 0x60, 0xcc, 0xfc, 0x00, // 2
+	0x78, 0xcc, 0x0c, 0x38, 0x0c, 0xcc, 0x78, 0x00, // 3
+	0x1c, 0x3c, 0x6c, 0xcc, 0xfe, 0x0c, 0x1e, 0x00, // 4
+	0xfc, 0xc0, 0xf8, 0x0c, 0x0c, 0xcc, 0x78, 0x00, // 5
+	0x38, 0x60, 0xc0, 0xf8, 0xcc, 0xcc, 0x78, 0x00, // 6
+	0xfc, 0xcc, 0x0c, 0x18, 0x30, 0x30, 0x30, 0x00, // 7
+	0x78, 0xcc, 0xcc, 0x78, 0xcc, 0xcc, 0x78, 0x00, // 8
+	0x78, 0xcc, 0xcc, 0x7c, 0x0c, 0x18, 0x70, 0x00, // 9
+	0x00, 0x30, 0x30, 0x00, 0x00, 0x30, 0x30, 0x00, // :
+	0x00, 0x30, 0x30, 0x00, 0x00, 0x30, 0x30, 0x60, // ;
+	0x18, 0x30, 0x60, 0xc0, 0x60, 0x30, 0x18, 0x00, // <
+	0x00, 0x00, 0xfc, 0x00, 0x00, 0xfc, 0x00, 0x00, // =
+	0x60, 0x30, 0x18, 0x0c, 0x18, 0x30, 0x60, 0x00, // >
+	0x78, 0xcc, 0x0c, 0x18, 0x30, 0x00, 0x30, 0x00, // ?
+	0x7c, 0xc6, 0xde, 0xde, 0xde, 0xc0, 0x78, 0x00, // @
+	0x30, 0x78, 0xcc, 0xcc, 0xfc, 0xcc, 0xcc, 0x00, // A
+	0xfc, 0x66, 0x66, 0x7c, 0x66, 0x66, 0xfc, 0x00, // B
+	0x3c, 0x66, 0xc0, 0xc0, 0xc0, 0x66, 0x3c, 0x00, // C
+	0xf8, 0x6c, 0x66, 0x66, 0x66, 0x6c, 0xf8, 0x00, // D
+	0xfe, 0x62, 0x68, 0x78, 0x68, 0x62, 0xfe, 0x00, // E
+	0xfe, 0x62, 0x68, 0x78, 0x68, 0x60, 0xf0, 0x00, // F
+	0x3c, 0x66, 0xc0, 0xc0, 0xce, 0x66, 0x3e, 0x00, // G
+	0xcc, 0xcc, 0xcc, 0xfc, 0xcc, 0xcc, 0xcc, 0x00, // H
+	0x78, 0x30, 0x30, 0x30, 0x30, 0x30, 0x78, 0x00, // I
+	0x1e, 0x0c, 0x0c, 0x0c, 0xcc, 0xcc, 0x78, 0x00, // J
+	0xe6, 0x66, 0x6c, 0x78, 0x6c, 0x66, 0xe6, 0x00, // K
+	0xf0, 0x60, 0x60, 0x60, 0x62, 0x66, 0xfe, 0x00, // L
+	0xc6, 0xee, 0xfe, 0xfe, 0xd6, 0xc6, 0xc6, 0x00, // M
+	0xc6, 0xe6, 0xf6, 0xde, 0xce, 0xc6, 0xc6, 0x00, // N
+	0x38, 0x6c, 0xc6, 0xc6, 0xc6, 0x6c, 0x38, 0x00, // O
+	0xfc, 0x66, 0x66, 0x7c, 0x60, 0x60, 0xf0, 0x00, // P
+	0x78, 0xcc, 0xcc, 0xcc, 0xdc, 0x78, 0x1c, 0x00, // Q
+	0xfc, 0x66, 0x66, 0x7c, 0x6c, 0x66, 0xe6, 0x00, // R
+	0x78, 0xcc, 0xe0, 0x70, 0x1c, 0xcc, 0x78, 0x00, // S
+	0xfc, 0xb4, 0x30, 0x30, 0x30, 0x30, 0x78, 0x00, // T
+	0xcc, 0xcc, 0xcc, 0xcc, 0xcc, 0xcc, 0xfc, 0x00, // U
+	0xcc, 0xcc, 0xcc, 0xcc, 0xcc, 0x78, 0x30, 0x00, // V
+	0xc6, 0xc6, 0xc6, 0xd6, 0xfe, 0xee, 0xc6, 0x00, // W
+	0xc6, 0xc6, 0x6c, 0x38, 0x38, 0x6c, 0xc6, 0x00, // X
+	0xcc, 0xcc, 0xcc, 0x78, 0x30, 0x30, 0x78, 0x00, // Y
+	0xfe, 0xc6, 0x8c, 0x18, 0x32, 0x66, 0xfe, 0x00, // Z
+	0x78, 0x60, 0x60, 0x60, 0x60, 0x60, 0x78, 0x00, // [
+	0xc0, 0x60, 0x30, 0x18, 0x0c, 0x06, 0x02, 0x00, // backslash
+	0x78, 0x18, 0x18, 0x18, 0x18, 0x18, 0x78, 0x00, // ]
+	0x10, 0x38, 0x6c, 0xc6, 0x00, 0x00, 0x00, 0x00, // ^
+	0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xff, // _
+	0x30, 0x30, 0x18, 0x00, 0x00, 0x00, 0x00, 0x00, // `
+	0x00, 0x00, 0x78, 0x0c, 0x7c, 0xcc, 0x76, 0x00, // a
+	0xe0, 0x60, 0x60, 0x7c, 0x66, 0x66, 0xdc, 0x00, // b
+	0x00, 0x00, 0x78, 0xcc, 0xc0, 0xcc, 0x78, 0x00, // c
+	0x1c, 0x0c, 0x0c, 0x7c, 0xcc, 0xcc, 0x76, 0x00, // d
+	0x00, 0x00, 0x78, 0xcc, 0xfc, 0xc0, 0x78, 0x00, // e
+	0x38, 0x6c, 0x60, 0xf0, 0x60, 0x60, 0xf0, 0x00, // f
+	0x00, 0x00, 0x76, 0xcc, 0xcc, 0x7c, 0x0c, 0xf8, // g
+	0xe0, 0x60, 0x6c, 0x76, 0x66, 0x66, 0xe6, 0x00, // h
+	0x30, 0x00, 0x70, 0x30, 0x30, 0x30, 0x78, 0x00, // i
+	0x0c, 0x00, 0x0c, 0x0c, 0x0c, 0xcc, 0xcc, 0x78, // j
+	0xe0, 0x60, 0x66, 0x6c, 0x78, 0x6c, 0xe6, 0x00, // k
+	0x70, 0x30, 0x30, 0x30, 0x30, 0x30, 0x78, 0x00, // l
+	0x00, 0x00, 0xcc, 0xfe, 0xfe, 0xd6, 0xc6, 0x00, // m
+	0x00, 0x00, 0xf8, 0xcc, 0xcc, 0xcc, 0xcc, 0x00, // n
+	0x00, 0x00, 0x78, 0xcc, 0xcc, 0xcc, 0x78, 0x00, // o
+	0x00, 0x00, 0xdc, 0x66, 0x66, 0x7c, 0x60, 0xf0, // p
+	0x00, 0x00, 0x76, 0xcc, 0xcc, 0x7c, 0x0c, 0x1e, // q
+	0x00, 0x00, 0xdc, 0x76, 0x66, 0x60, 0xf0, 0x00, // r
+	0x00, 0x00, 0x7c, 0xc0, 0x78, 0x0c, 0xf8, 0x00, // s
+	0x10, 0x30, 0x7c, 0x30, 0x30, 0x34, 0x18, 0x00, // t
+	0x00, 0x00, 0xcc, 0xcc, 0xcc, 0xcc, 0x76, 0x00, // u
+	0x00, 0x00, 0xcc, 0xcc, 0xcc, 0x78, 0x30, 0x00, // v
+	0x00, 0x00, 0xc6, 0xd6, 0xfe, 0xfe, 0x6c, 0x00, // w
+	0x00, 0x00, 0xc6, 0x6c, 0x38, 0x6c, 0xc6, 0x00, // x
+	0x00, 0x00, 0xcc, 0xcc, 0xcc, 0x7c, 0x0c, 0xf8, // y
+	0x00, 0x00, 0xfc, 0x98, 0x30, 0x64, 0xfc, 0x00, // z
+	0x1c, 0x30, 0x30, 0xe0, 0x30, 0x30, 0x1c, 0x00, // {
+	0x18, 0x18, 0x18, 0x00, 0x18, 0x18, 0x18, 0x00, // |
+	0xe0, 0x30, 0x30, 0x1c, 0x30, 0x30, 0xe0, 0x00, // }
+	0x76, 0xdc, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // ~
+];
+
+static FONT_8X16_DATA: [u8; 95 * 16] = [
+	0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //  
+	0x00, 0x00, 0x00, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x00, 0x10, 0x00, 0x00, 0x00, 0x00, // !
+	0x00, 0x00, 0x00, 0x24, 0x24, 0x24, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // "
+	0x00, 0x00, 0x00, 0x00, 0x24, 0x24, 0x7e, 0x24, 0x7e, 0x24, 0x24, 0x00, 0x00, 0x00, 0x00, 0x00, // #
+	0x00, 0x00, 0x00, 0x10, 0x3c, 0x50, 0x50, 0x38, 0x14, 0x14, 0x78, 0x10, 0x00, 0x00, 0x00, 0x00, // $
+	0x00, 0x00, 0x00, 0x22, 0x52, 0x24, 0x08, 0x08, 0x10, 0x24, 0x2a, 0x44, 0x00, 0x00, 0x00, 0x00, // %
+	0x00, 0x00, 0x00, 0x00, 0x00, 0x30, 0x48, 0x48, 0x30, 0x4a, 0x44, 0x3a, 0x00, 0x00, 0x00, 0x00, // &
+	0x00, 0x00, 0x00, 0x10, 0x10, 0x10, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // '
+	0x00, 0x00, 0x00, 0x04, 0x08, 0x08, 0x10, 0x10, 0x10, 0x08, 0x08, 0x04, 0x00, 0x00, 0x00, 0x00, // (
+	0x00, 0x00, 0x00, 0x20, 0x10, 0x10, 0x08, 0x08, 0x08, 0x10, 0x10, 0x20, 0x00, 0x00, 0x00, 0x00, // )
+	0x00, 0x00, 0x00, 0x24, 0x18, 0x7e, 0x18, 0x24, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // *
+	0x00, 0x00, 0x00, 0x00, 0x00, 0x10, 0x10, 0x7c, 0x10, 0x10, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // +
+	0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x38, 0x30, 0x40, 0x00, 0x00, 0x00, // ,
+	0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x7c, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // -
+	0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x10, 0x38, 0x10, 0x00, 0x00, 0x00, // .
+	0x00, 0x00, 0x00, 0x02, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x80, 0x80, 0x00, 0x00, 0x00, 0x00, // /
+	0x00, 0x00, 0x00, 0x18, 0x24, 0x42, 0x42, 0x42, 0x42, 0x42, 0x24, 0x18, 0x00, 0x00, 0x00, 0x00, // 0
+	0x00, 0x00, 0x00, 0x10, 0x30, 0x50, 0x10, 0x10, 0x10, 0x10, 0x10, 0x7c, 0x00, 0x00, 0x00, 0x00, // 1
+	0x00, 0x00, 0x00, 0x3c, 0x42, 0x42, 0x02, 0x04, 0x18, 0x20, 0x40, 0x7e, 0x00, 0x00, 0x00, 0x00, // 2
+	0x00, 0x00, 0x00, 0x7e, 0x02, 0x04, 0x08, 0x1c, 0x02, 0x02, 0x42, 0x3c, 0x00, 0x00, 0x00, 0x00, // 3
+	0x00, 0x00, 0x00, 0x04, 0x0c, 0x14, 0x24, 0x44, 0x44, 0x7e, 0x04, 0x04, 0x00, 0x00, 0x00, 0x00, // 4
+	0x00, 0x00, 0x00, 0x7e, 0x40, 0x40, 0x5c, 0x62, 0x02, 0x02, 0x42, 0x3c, 0x00, 0x00, 0x00, 0x00, // 5
+	0x00, 0x00, 0x00, 0x1c, 0x20, 0x40, 0x40, 0x5c, 0x62, 0x42, 0x42, 0x3c, 0x00, 0x00, 0x00, 0x00, // 6
+	0x00, 0x00, 0x00, 0x7e, 0x02, 0x04, 0x08, 0x08, 0x10, 0x10, 0x20, 0x20, 0x00, 0x00, 0x00, 0x00, // 7
+	0x00, 0x00, 0x00, 0x3c, 0x42, 0x42, 0x42, 0x3c, 0x42, 0x42, 0x42, 0x3c, 0x00, 0x00, 0x00, 0x00, // 8
+	0x00, 0x00, 0x00, 0x3c, 0x42, 0x42, 0x46, 0x3a, 0x02, 0x02, 0x04, 0x38, 0x00, 0x00, 0x00, 0x00, // 9
+	0x00, 0x00, 0x00, 0x00, 0x00, 0x10, 0x38, 0x10, 0x00, 0x00, 0x10, 0x38, 0x10, 0x00, 0x00, 0x00, // :
+	0x00, 0x00, 0x00, 0x00, 0x00, 0x10, 0x38, 0x10, 0x00, 0x00, 0x38, 0x30, 0x40, 0x00, 0x00, 0x00, // ;
+	0x00, 0x00, 0x00, 0x02, 0x04, 0x08, 0x10, 0x20, 0x10, 0x08, 0x04, 0x02, 0x00, 0x00, 0x00, 0x00, // <
+	0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x7e, 0x00, 0x00, 0x7e, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // =
+	0x00, 0x00, 0x00, 0x40, 0x20, 0x10, 0x08, 0x04, 0x08, 0x10, 0x20, 0x40, 0x00, 0x00, 0x00, 0x00, // >
+	0x00, 0x00, 0x00, 0x3c, 0x42, 0x42, 0x02, 0x04, 0x08, 0x08, 0x00, 0x08, 0x00, 0x00, 0x00, 0x00, // ?
+	0x00, 0x00, 0x00, 0x3c, 0x42, 0x42, 0x4e, 0x52, 0x56, 0x4a, 0x40, 0x3c, 0x00, 0x00, 0x00, 0x00, // @
+	0x00, 0x00, 0x00, 0x18, 0x24, 0x42, 0x42, 0x42, 0x7e, 0x42, 0x42, 0x42, 0x00, 0x00, 0x00, 0x00, // A
+	0x00, 0x00, 0x00, 0x78, 0x44, 0x42, 0x44, 0x78, 0x44, 0x42, 0x44, 0x78, 0x00, 0x00, 0x00, 0x00, // B
+	0x00, 0x00, 0x00, 0x3c, 0x42, 0x40, 0x40, 0x40, 0x40, 0x40, 0x42, 0x3c, 0x00, 0x00, 0x00, 0x00, // C
+	0x00, 0x00, 0x00, 0x78, 0x44, 0x42, 0x42, 0x42, 0x42, 0x42, 0x44, 0x78, 0x00, 0x00, 0x00, 0x00, // D
+	0x00, 0x00, 0x00, 0x7e, 0x40, 0x40, 0x40, 0x78, 0x40, 0x40, 0x40, 0x7e, 0x00, 0x00, 0x00, 0x00, // E
+	0x00, 0x00, 0x00, 0x7e, 0x40, 0x40, 0x40, 0x78, 0x40, 0x40, 0x40, 0x40, 0x00, 0x00, 0x00, 0x00, // F
+	0x00, 0x00, 0x00, 0x3c, 0x42, 0x40, 0x40, 0x40, 0x4e, 0x42, 0x46, 0x3a, 0x00, 0x00, 0x00, 0x00, // G
+	0x00, 0x00, 0x00, 0x42, 0x42, 0x42, 0x42, 0x7e, 0x42, 0x42, 0x42, 0x42, 0x00, 0x00, 0x00, 0x00, // H
+	0x00, 0x00, 0x00, 0x7c, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x7c, 0x00, 0x00, 0x00, 0x00, // I
+	0x00, 0x00, 0x00, 0x1f, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x44, 0x38, 0x00, 0x00, 0x00, 0x00, // J
+	0x00, 0x00, 0x00, 0x42, 0x44, 0x48, 0x50, 0x60, 0x50, 0x48, 0x44, 0x42, 0x00, 0x00, 0x00, 0x00, // K
+	0x00, 0x00, 0x00, 0x40, 0x40, 0x40, 0x40, 0x40, 0x40, 0x40, 0x40, 0x7e, 0x00, 0x00, 0x00, 0x00, // L
+	0x00, 0x00, 0x00, 0x82, 0x82, 0xc6, 0xaa, 0x92, 0x92, 0x82, 0x82, 0x82, 0x00, 0x00, 0x00, 0x00, // M
+	0x00, 0x00, 0x00, 0x42, 0x42, 0x62, 0x52, 0x4a, 0x46, 0x42, 0x42, 0x42, 0x00, 0x00, 0x00, 0x00, // N
+	0x00, 0x00, 0x00, 0x3c, 0x42, 0x42, 0x42, 0x42, 0x42, 0x42, 0x42, 0x3c, 0x00, 0x00, 0x00, 0x00, // O
+	0x00, 0x00, 0x00, 0x7c, 0x42, 0x42, 0x42, 0x7c, 0x40, 0x40, 0x40, 0x40, 0x00, 0x00, 0x00, 0x00, // P
+	0x00, 0x00, 0x00, 0x3c, 0x42, 0x42, 0x42, 0x42, 0x42, 0x52, 0x4a, 0x3c, 0x02, 0x00, 0x00, 0x00, // Q
+	0x00, 0x00, 0x00, 0x7c, 0x42, 0x42, 0x42, 0x7c, 0x50, 0x48, 0x44, 0x42, 0x00, 0x00, 0x00, 0x00, // R
+	0x00, 0x00, 0x00, 0x3c, 0x42, 0x40, 0x40, 0x3c, 0x02, 0x02, 0x42, 0x3c, 0x00, 0x00, 0x00, 0x00, // S
+	0x00, 0x00, 0x00, 0xfe, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x00, 0x00, 0x00, 0x00, // T
+	0x00, 0x00, 0x00, 0x42, 0x42, 0x42, 0x42, 0x42, 0x42, 0x42, 0x42, 0x3c, 0x00, 0x00, 0x00, 0x00, // U
+	0x00, 0x00, 0x00, 0x82, 0x82, 0x44, 0x44, 0x44, 0x28, 0x28, 0x28, 0x10, 0x00, 0x00, 0x00, 0x00, // V
+	0x00, 0x00, 0x00, 0x82, 0x82, 0x82, 0x82, 0x92, 0x92, 0x92, 0xaa, 0x44, 0x00, 0x00, 0x00, 0x00, // W
+	0x00, 0x00, 0x00, 0x82, 0x82, 0x44, 0x28, 0x10, 0x28, 0x44, 0x82, 0x82, 0x00, 0x00, 0x00, 0x00, // X
+	0x00, 0x00, 0x00, 0x82, 0x82, 0x44, 0x28, 0x10, 0x10, 0x10, 0x10, 0x10, 0x00, 0x00, 0x00, 0x00, // Y
+	0x00, 0x00, 0x00, 0x7e, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x40, 0x7e, 0x00, 0x00, 0x00, 0x00, // Z
+	0x00, 0x00, 0x00, 0x3c, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x3c, 0x00, 0x00, 0x00, 0x00, // [
+	0x00, 0x00, 0x00, 0x80, 0x80, 0x40, 0x20, 0x10, 0x08, 0x04, 0x02, 0x02, 0x00, 0x00, 0x00, 0x00, // backslash
+	0x00, 0x00, 0x00, 0x78, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x78, 0x00, 0x00, 0x00, 0x00, // ]
+	0x00, 0x00, 0x00, 0x10, 0x28, 0x44, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // ^
+	0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xfe, 0x00, 0x00, 0x00, // _
+	0x00, 0x00, 0x10, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // `
+	0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x3c, 0x02, 0x3e, 0x42, 0x46, 0x3a, 0x00, 0x00, 0x00, 0x00, // a
+	0x00, 0x00, 0x00, 0x40, 0x40, 0x40, 0x5c, 0x62, 0x42, 0x42, 0x62, 0x5c, 0x00, 0x00, 0x00, 0x00, // b
+	0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x3c, 0x42, 0x40, 0x40, 0x42, 0x3c, 0x00, 0x00, 0x00, 0x00, // c
+	0x00, 0x00, 0x00, 0x02, 0x02, 0x02, 0x3a, 0x46, 0x42, 0x42, 0x46, 0x3a, 0x00, 0x00, 0x00, 0x00, // d
+	0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x3c, 0x42, 0x7e, 0x40, 0x42, 0x3c, 0x00, 0x00, 0x00, 0x00, // e
+	0x00, 0x00, 0x00, 0x1c, 0x22, 0x20, 0x20, 0x7c, 0x20, 0x20, 0x20, 0x20, 0x00, 0x00, 0x00, 0x00, // f
+	0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x3a, 0x44, 0x44, 0x38, 0x40, 0x3c, 0x42, 0x3c, 0x00, 0x00, // g
+	0x00, 0x00, 0x00, 0x40, 0x40, 0x40, 0x5c, 0x62, 0x42, 0x42, 0x42, 0x42, 0x00, 0x00, 0x00, 0x00, // h
+	0x00, 0x00, 0x00, 0x00, 0x10, 0x00, 0x30, 0x10, 0x10, 0x10, 0x10, 0x7c, 0x00, 0x00, 0x00, 0x00, // i
+	0x00, 0x00, 0x00, 0x00, 0x04, 0x00, 0x0c, 0x04, 0x04, 0x04, 0x04, 0x44, 0x44, 0x38, 0x00, 0x00, // j
+	0x00, 0x00, 0x00, 0x40, 0x40, 0x40, 0x44, 0x48, 0x70, 0x48, 0x44, 0x42, 0x00, 0x00, 0x00, 0x00, // k
+	0x00, 0x00, 0x00, 0x30, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x7c, 0x00, 0x00, 0x00, 0x00, // l
+	0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xec, 0x92, 0x92, 0x92, 0x92, 0x82, 0x00, 0x00, 0x00, 0x00, // m
+	0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x5c, 0x62, 0x42, 0x42, 0x42, 0x42, 0x00, 0x00, 0x00, 0x00, // n
+	0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x3c, 0x42, 0x42, 0x42, 0x42, 0x3c, 0x00, 0x00, 0x00, 0x00, // o
+	0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x5c, 0x62, 0x42, 0x62, 0x5c, 0x40, 0x40, 0x40, 0x00, 0x00, // p
+	0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x3a, 0x46, 0x42, 0x46, 0x3a, 0x02, 0x02, 0x02, 0x00, 0x00, // q
+	0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x5c, 0x22, 0x20, 0x20, 0x20, 0x20, 0x00, 0x00, 0x00, 0x00, // r
+	0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x3c, 0x42, 0x30, 0x0c, 0x42, 0x3c, 0x00, 0x00, 0x00, 0x00, // s
+	0x00, 0x00, 0x00, 0x00, 0x20, 0x20, 0x7c, 0x20, 0x20, 0x20, 0x22, 0x1c, 0x00, 0x00, 0x00, 0x00, // t
+	0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x44, 0x44, 0x44, 0x44, 0x44, 0x3a, 0x00, 0x00, 0x00, 0x00, // u
+	0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x44, 0x44, 0x44, 0x28, 0x28, 0x10, 0x00, 0x00, 0x00, 0x00, // v
+	0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x82, 0x82, 0x92, 0x92, 0xaa, 0x44, 0x00, 0x00, 0x00, 0x00, // w
+	0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x42, 0x24, 0x18, 0x18, 0x24, 0x42, 0x00, 0x00, 0x00, 0x00, // x
+	0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x42, 0x42, 0x42, 0x46, 0x3a, 0x02, 0x42, 0x3c, 0x00, 0x00, // y
+	0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x7e, 0x04, 0x08, 0x10, 0x20, 0x7e, 0x00, 0x00, 0x00, 0x00, // z
+	0x00, 0x00, 0x00, 0x0e, 0x10, 0x10, 0x08, 0x30, 0x08, 0x10, 0x10, 0x0e, 0x00, 0x00, 0x00, 0x00, // {
+	0x00, 0x00, 0x00, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x00, 0x00, 0x00, 0x00, // |
+	0x00, 0x00, 0x00, 0x70, 0x08, 0x08, 0x10, 0x0c, 0x10, 0x08, 0x08, 0x70, 0x00, 0x00, 0x00, 0x00, // }
+	0x00, 0x00, 0x00, 0x24, 0x54, 0x48, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // ~
+];
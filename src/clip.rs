@@ -0,0 +1,180 @@
+use crate::vector::{vec2f, vec3f, vec4f};
+
+// Clipping happens in clip space, after the mvp matrix and before the perspective divide.
+// The projection matrix puts the near plane at z = 0 and the far plane at z = w,
+// x and y are visible between -w and w. Everything in here is linear, so the
+// vertex attributes can be interpolated with the same factor as the position.
+
+// a triangle clipped against all six planes can end up with at most one extra vertex per plane
+pub const MAX_CLIP_VERTICES: usize = 9;
+
+#[derive(Clone, Copy)]
+pub struct ClipVertex
+{
+	pub position: vec4f,
+	pub color: vec3f,
+	pub uv: vec2f
+}
+
+#[derive(Clone, Copy)]
+pub enum ClipPlane
+{
+	Left,
+	Right,
+	Bottom,
+	Top,
+	Near,
+	Far
+}
+
+pub const CLIP_PLANES: [ClipPlane; 6] = [
+	ClipPlane::Left,
+	ClipPlane::Right,
+	ClipPlane::Bottom,
+	ClipPlane::Top,
+	ClipPlane::Near,
+	ClipPlane::Far
+];
+
+impl ClipPlane
+{
+	// signed distance, anything below 0 is outside of the plane
+	pub fn distance(&self, point: &vec4f) -> f32
+	{
+		match self
+		{
+			ClipPlane::Left => point.w + point.x,
+			ClipPlane::Right => point.w - point.x,
+			ClipPlane::Bottom => point.w + point.y,
+			ClipPlane::Top => point.w - point.y,
+			ClipPlane::Near => point.z,
+			ClipPlane::Far => point.w - point.z
+		}
+	}
+}
+
+impl ClipVertex
+{
+	pub fn new(position: vec4f) -> ClipVertex
+	{
+		ClipVertex{
+			position,
+			color: vec3f::new(),
+			uv: vec2f::new()
+		}
+	}
+
+	pub fn lerp(&self, other: &ClipVertex, t: f32) -> ClipVertex
+	{
+		ClipVertex{
+			position: self.position.add(&other.position.sub(&self.position).scale(t)),
+			color: self.color.add(&other.color.sub(&self.color).scale(t)),
+			uv: self.uv.add(&other.uv.sub(&self.uv).scale(t))
+		}
+	}
+}
+
+// sutherland-hodgman against every plane, returns how many vertices of out_polygon are used
+// the resulting convex polygon keeps the winding of the input triangle
+pub fn clip_triangle(triangle: &[ClipVertex; 3], out_polygon: &mut [ClipVertex; MAX_CLIP_VERTICES]) -> usize
+{
+	let mut scratch = [triangle[0]; MAX_CLIP_VERTICES];
+
+	out_polygon[..3].copy_from_slice(triangle);
+	let mut count = 3;
+
+	for plane in CLIP_PLANES
+	{
+		scratch[..count].copy_from_slice(&out_polygon[..count]);
+		count = clip_polygon_against(plane, &scratch[..count], out_polygon);
+
+		if count == 0
+		{
+			break;
+		}
+	}
+
+	count
+}
+
+// clip a convex polygon against one plane, out_polygon has to be big enough for one extra vertex
+fn clip_polygon_against(plane: ClipPlane, polygon: &[ClipVertex], out_polygon: &mut [ClipVertex]) -> usize
+{
+	let mut count = 0;
+
+	for i in 0..polygon.len()
+	{
+		let previous = &polygon[(i + polygon.len() - 1) % polygon.len()];
+		let current = &polygon[i];
+
+		let previous_distance = plane.distance(&previous.position);
+		let current_distance = plane.distance(&current.position);
+
+		if current_distance >= 0.0f32
+		{
+			if previous_distance < 0.0f32
+			{
+				out_polygon[count] = previous.lerp(current, previous_distance / (previous_distance - current_distance));
+				count += 1;
+			}
+
+			out_polygon[count] = *current;
+			count += 1;
+		}
+		else if previous_distance >= 0.0f32
+		{
+			out_polygon[count] = previous.lerp(current, previous_distance / (previous_distance - current_distance));
+			count += 1;
+		}
+	}
+
+	count
+}
+
+// calls draw for every triangle of the clipped triangle, the clipped polygon is split into a fan
+pub fn for_each_clipped_triangle<F: FnMut(&ClipVertex, &ClipVertex, &ClipVertex)>(triangle: &[ClipVertex; 3], mut draw: F)
+{
+	let mut polygon = [triangle[0]; MAX_CLIP_VERTICES];
+	let count = clip_triangle(triangle, &mut polygon);
+
+	for i in 1..count.saturating_sub(1)
+	{
+		draw(&polygon[0], &polygon[i], &polygon[i + 1]);
+	}
+}
+
+// liang-barsky style clip of a line segment, None if nothing of it is visible
+pub fn clip_line(begin: &vec4f, end: &vec4f) -> Option<(vec4f, vec4f)>
+{
+	let mut t_begin = 0.0f32;
+	let mut t_end = 1.0f32;
+
+	for plane in CLIP_PLANES
+	{
+		let begin_distance = plane.distance(begin);
+		let end_distance = plane.distance(end);
+
+		if begin_distance < 0.0f32 && end_distance < 0.0f32
+		{
+			return None;
+		}
+
+		if begin_distance < 0.0f32
+		{
+			t_begin = f32::max(t_begin, begin_distance / (begin_distance - end_distance));
+		}
+		else if end_distance < 0.0f32
+		{
+			t_end = f32::min(t_end, begin_distance / (begin_distance - end_distance));
+		}
+	}
+
+	if t_begin > t_end
+	{
+		return None;
+	}
+
+	let direction = end.sub(begin);
+
+	Some((begin.add(&direction.scale(t_begin)), begin.add(&direction.scale(t_end))))
+}
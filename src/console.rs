@@ -0,0 +1,132 @@
+use core::fmt;
+use crate::vector::vec2f;
+use crate::framebuffer::Surface;
+use crate::font::{Font, draw_text};
+use crate::layout::TextRect;
+
+// A text console for write!() and friends. Text is kept in a fixed grid of up to ROWS lines of
+// COLUMNS bytes and drawn with draw() every frame, so it survives the framebuffer being cleared.
+// Lines wrap when the next character wouldn't fit into the console's rectangle, and once the
+// last row is full everything scrolls up by a line.
+// '\n' starts a new line, '\r' goes back to the start of the current one and clears it,
+// anything outside of ASCII is shown as '?'.
+pub struct Console<F: Font, const ROWS: usize, const COLUMNS: usize>
+{
+	pub font: F,
+	pub rect: TextRect,
+	pub color: u8,
+	lines: [[u8; COLUMNS]; ROWS],
+	lengths: [usize; ROWS],
+	// row the cursor is on and how far along it is in pixels
+	cursor_row: usize,
+	cursor_x: f32
+}
+
+impl<F: Font, const ROWS: usize, const COLUMNS: usize> Console<F, ROWS, COLUMNS>
+{
+	pub fn new(font: F, rect: TextRect, color: u8) -> Console<F, ROWS, COLUMNS>
+	{
+		// fails the build for a console without a row or column to put characters into
+		const { assert!(ROWS > 0 && COLUMNS > 0, "a Console needs at least one row and one column") };
+
+		Console{
+			font,
+			rect,
+			color,
+			lines: [[0u8; COLUMNS]; ROWS],
+			lengths: [0usize; ROWS],
+			cursor_row: 0,
+			cursor_x: 0.0f32
+		}
+	}
+
+	// rows that fit into the rectangle, at least one
+	pub fn row_count(&self) -> usize
+	{
+		let fitting = libm::floorf(self.rect.height / self.font.line_height()) as usize;
+		fitting.clamp(1, ROWS)
+	}
+
+	// row and column the next character goes to
+	pub fn cursor(&self) -> (usize, usize)
+	{
+		(self.cursor_row, self.lengths[self.cursor_row])
+	}
+
+	pub fn clear(&mut self)
+	{
+		self.lengths = [0usize; ROWS];
+		self.cursor_row = 0;
+		self.cursor_x = 0.0f32;
+	}
+
+	pub fn put_char(&mut self, char: u8)
+	{
+		match char
+		{
+			b'\n' => self.new_line(),
+			b'\r' =>
+			{
+				self.lengths[self.cursor_row] = 0;
+				self.cursor_x = 0.0f32;
+			},
+			_ =>
+			{
+				let advance = self.font.advance(char);
+				let length = self.lengths[self.cursor_row];
+
+				// an empty line takes the character even if it's too wide, or nothing would ever be written
+				if length == COLUMNS || (length > 0 && self.cursor_x + advance > self.rect.width)
+				{
+					self.new_line();
+				}
+
+				let length = self.lengths[self.cursor_row];
+				self.lines[self.cursor_row][length] = char;
+				self.lengths[self.cursor_row] = length + 1;
+				self.cursor_x += advance;
+			}
+		}
+	}
+
+	pub fn draw(&self, framebuffer: &mut Surface)
+	{
+		let line_height = self.font.line_height();
+
+		for row in 0..=self.cursor_row
+		{
+			let position = vec2f{x: self.rect.x, y: self.rect.y + row as f32 * line_height};
+			draw_text(framebuffer, &self.font, &self.lines[row][..self.lengths[row]], position, self.color);
+		}
+	}
+
+	fn new_line(&mut self)
+	{
+		if self.cursor_row + 1 < self.row_count()
+		{
+			self.cursor_row += 1;
+		}
+		else
+		{
+			// scroll, the top line goes away
+			self.lines.copy_within(1..=self.cursor_row, 0);
+			self.lengths.copy_within(1..=self.cursor_row, 0);
+		}
+
+		self.lengths[self.cursor_row] = 0;
+		self.cursor_x = 0.0f32;
+	}
+}
+
+impl<F: Font, const ROWS: usize, const COLUMNS: usize> fmt::Write for Console<F, ROWS, COLUMNS>
+{
+	fn write_str(&mut self, s: &str) -> fmt::Result
+	{
+		for char in s.chars()
+		{
+			self.put_char(if char.is_ascii() { char as u8 } else { b'?' });
+		}
+
+		Ok(())
+	}
+}
@@ -0,0 +1,184 @@
+// Damage tracking: a Surface with a DamageList attached records the area every draw call
+// touches, so only those parts of the screen have to be sent to the display.
+// The list stays short by merging: a rectangle that overlaps or nearly touches one that's
+// already there is merged into it, and once the list is full the new one goes into whichever
+// rectangle grows the least from it.
+
+pub const MAX_DAMAGE_RECTS: usize = 8;
+
+// rectangles closer than this are merged, a few extra pixels are cheaper than another address window
+const MERGE_DISTANCE: usize = 8;
+
+// inclusive pixel bounds, the same as St7789::set_address_window takes them
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct DamageRect
+{
+	pub left: usize,
+	pub top: usize,
+	pub right: usize,
+	pub bottom: usize
+}
+
+impl DamageRect
+{
+	// empty for a zero width or height
+	pub fn new(x: usize, y: usize, width: usize, height: usize) -> Option<DamageRect>
+	{
+		if width == 0 || height == 0
+		{
+			return None;
+		}
+
+		Some(DamageRect{
+			left: x,
+			top: y,
+			right: x + width - 1,
+			bottom: y + height - 1
+		})
+	}
+
+	pub fn width(&self) -> usize
+	{
+		self.right - self.left + 1
+	}
+
+	pub fn height(&self) -> usize
+	{
+		self.bottom - self.top + 1
+	}
+
+	pub fn area(&self) -> usize
+	{
+		self.width() * self.height()
+	}
+
+	pub fn contains(&self, other: &DamageRect) -> bool
+	{
+		other.left >= self.left && other.right <= self.right && other.top >= self.top && other.bottom <= self.bottom
+	}
+
+	pub fn union(&self, other: &DamageRect) -> DamageRect
+	{
+		DamageRect{
+			left: usize::min(self.left, other.left),
+			top: usize::min(self.top, other.top),
+			right: usize::max(self.right, other.right),
+			bottom: usize::max(self.bottom, other.bottom)
+		}
+	}
+
+	// overlapping or less than distance pixels apart
+	fn is_near(&self, other: &DamageRect, distance: usize) -> bool
+	{
+		other.left <= self.right + distance && self.left <= other.right + distance &&
+			other.top <= self.bottom + distance && self.top <= other.bottom + distance
+	}
+}
+
+#[derive(Clone, Copy)]
+pub struct DamageList
+{
+	rects: [DamageRect; MAX_DAMAGE_RECTS],
+	count: usize
+}
+
+impl DamageList
+{
+	pub const fn new() -> DamageList
+	{
+		DamageList{
+			rects: [DamageRect{left: 0, top: 0, right: 0, bottom: 0}; MAX_DAMAGE_RECTS],
+			count: 0
+		}
+	}
+
+	pub fn rects(&self) -> &[DamageRect]
+	{
+		&self.rects[..self.count]
+	}
+
+	pub fn is_empty(&self) -> bool
+	{
+		self.count == 0
+	}
+
+	pub fn clear(&mut self)
+	{
+		self.count = 0;
+	}
+
+	// pixels covered by the rectangles, overlaps counted once per rectangle
+	pub fn area(&self) -> usize
+	{
+		self.rects().iter().map(|rect| rect.area()).sum()
+	}
+
+	pub fn add(&mut self, rect: DamageRect)
+	{
+		// drawing tends to continue right where it left off, so the rectangle added to last is checked first
+		if self.count > 0 && self.rects[self.count - 1].contains(&rect)
+		{
+			return;
+		}
+
+		let mut rect = rect;
+
+		// merging can bring the rectangle close to others, keep going until nothing is near it
+		while let Some(index) = self.rects().iter().position(|other| other.is_near(&rect, MERGE_DISTANCE))
+		{
+			rect = rect.union(&self.rects[index]);
+			self.remove(index);
+		}
+
+		if self.count == MAX_DAMAGE_RECTS
+		{
+			let (index, _) = self.rects().iter().enumerate()
+				.min_by_key(|(_, other)| other.union(&rect).area() - other.area())
+				.unwrap();
+
+			rect = rect.union(&self.rects[index]);
+			self.remove(index);
+			// the bigger rectangle might reach others now
+			self.add(rect);
+			return;
+		}
+
+		self.rects[self.count] = rect;
+		self.count += 1;
+	}
+
+	pub fn add_list(&mut self, other: &DamageList)
+	{
+		for rect in other.rects()
+		{
+			self.add(*rect);
+		}
+	}
+
+	// [left, right) on row y
+	pub fn add_span(&mut self, y: usize, left: usize, right: usize)
+	{
+		if let Some(rect) = DamageRect::new(left, y, right.saturating_sub(left), 1)
+		{
+			self.add(rect);
+		}
+	}
+
+	pub fn add_pixel(&mut self, x: usize, y: usize)
+	{
+		self.add(DamageRect{left: x, top: y, right: x, bottom: y});
+	}
+
+	fn remove(&mut self, index: usize)
+	{
+		self.rects.copy_within(index + 1..self.count, index);
+		self.count -= 1;
+	}
+}
+
+impl Default for DamageList
+{
+	fn default() -> Self {
+		DamageList::new()
+	}
+}
@@ -0,0 +1,42 @@
+// Depth values are stored as u16 per pixel, laid out the same way as the framebuffer.
+// The projection matrix maps the near plane to z = 0 and the far plane to z = 1,
+// so a smaller value means the fragment is closer to the camera.
+
+pub const DEPTH_FAR: u16 = u16::MAX;
+
+pub fn clear(depthbuffer: &mut [u16])
+{
+	for depth in depthbuffer.iter_mut()
+	{
+		*depth = DEPTH_FAR;
+	}
+}
+
+// map the projected z from [0, 1] (near -> far) into the u16 range
+pub fn to_depth(z: f32) -> u16
+{
+	if z <= 0.0f32
+	{
+		0u16
+	}
+	else if z >= 1.0f32
+	{
+		DEPTH_FAR
+	}
+	else
+	{
+		(z * DEPTH_FAR as f32) as u16
+	}
+}
+
+// returns true and stores the new depth if the fragment is closer than what's already there
+pub fn test_and_set(depthbuffer: &mut [u16], index: usize, depth: u16) -> bool
+{
+	if depth < depthbuffer[index]
+	{
+		depthbuffer[index] = depth;
+		return true;
+	}
+
+	false
+}
@@ -0,0 +1,28 @@
+use crate::vector::vec2f;
+use crate::framebuffer::Surface;
+
+// What the stroke font (text::StrokeFont) and the bitmap fonts (bitmap_font::BitmapFont) have
+// in common, so text can be drawn without caring which kind of font it uses.
+// Positions are the top left corner of a character cell, everything is measured in pixels.
+pub trait Font
+{
+	// horizontal distance from one character to the next
+	fn advance(&self, char: u8) -> f32;
+	// distance from the top of a line to its baseline
+	fn ascent(&self) -> f32;
+	// distance between the tops of two lines
+	fn line_height(&self) -> f32;
+	fn draw_char(&self, framebuffer: &mut Surface, char: u8, position: vec2f, color: u8);
+}
+
+// a single line of text, position is the top left corner of the first character
+pub fn draw_text<F: Font>(framebuffer: &mut Surface, font: &F, str_in: &[u8], position: vec2f, color: u8)
+{
+	let mut x = position.x;
+
+	for char in str_in
+	{
+		font.draw_char(framebuffer, *char, vec2f{x, y: position.y}, color);
+		x += font.advance(*char);
+	}
+}
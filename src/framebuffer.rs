@@ -1,129 +1,526 @@
 use crate::vector::vec2f;
-use crate::pixel_ops::colors;
+use crate::depthbuffer;
+use crate::damage::{DamageList, DamageRect};
+use crate::line::{self, ClipRect, LineStyle};
+use crate::pixel_ops::{convert_pixel, make_pixel3, blend_pixel, blend_channel, split_pixel};
 
-pub fn draw_iter<T: Iterator<Item = u8>>(framebuffer: &mut [u8], x_begin: usize, y_begin: usize, x_end: usize, y_end: usize, mut pixels: T)
+// how the bytes of a surface are turned into colors once they leave it
+#[derive(Clone, Copy, PartialEq)]
+pub enum PixelFormat
 {
-	//let mut iter = pixels.into_iter();
+	Rgb332,
+	Gray8
+}
+
+impl PixelFormat
+{
+	// the display wants RGB 565
+	pub fn to_rgb565(&self, pix: u8) -> u16
+	{
+		match self
+		{
+			PixelFormat::Rgb332 => convert_pixel(pix),
+			PixelFormat::Gray8 => make_pixel3(pix, pix, pix)
+		}
+	}
 
-	for x in x_begin..x_end 
+	// store an RGB 332 color in this format, gray surfaces keep the luminance
+	pub fn from_rgb332(&self, pix: u8) -> u8
 	{
-		for y in y_begin..y_end
+		match self
 		{
-			match pixels.next()
+			PixelFormat::Rgb332 => pix,
+			PixelFormat::Gray8 =>
 			{
-				None => { return },
-				Some(pix) => { framebuffer[y * 240 + x] = pix; }
+				let (r, g, b) = split_pixel(pix);
+				((r as u16 * 77 + g as u16 * 150 + b as u16 * 29) >> 8) as u8
 			}
 		}
 	}
+
+	// mix src over dst, alpha 255 is all src
+	pub fn blend(&self, dst: u8, src: u8, alpha: u8) -> u8
+	{
+		match self
+		{
+			PixelFormat::Rgb332 => blend_pixel(dst, src, alpha),
+			PixelFormat::Gray8 => blend_channel(dst, src, alpha)
+		}
+	}
 }
 
-pub fn draw_array(framebuffer: &mut [u8], x_begin: usize, y_begin: usize, pixels: &[u8], width: usize)
+// A block of 8 bit pixels stored row by row.
+// stride is the distance between the starts of two rows, it's bigger than width
+// when the surface is a window into a bigger one (see sub_surface).
+// With a damage list attached every pixel written is recorded in it, offset by origin_x and
+// origin_y so surfaces covering different parts of the screen can share one list.
+pub struct Surface<'a>
 {
-	let height = pixels.len() / width;
+	pub data: &'a mut [u8],
+	pub width: usize,
+	pub height: usize,
+	pub stride: usize,
+	pub format: PixelFormat,
+	pub damage: Option<&'a mut DamageList>,
+	pub origin_x: usize,
+	pub origin_y: usize
+}
+
+impl<'a> Surface<'a>
+{
+	// data needs to hold at least width * height pixels
+	pub fn new(data: &'a mut [u8], width: usize, height: usize) -> Surface<'a>
+	{
+		Surface{
+			data,
+			width,
+			height,
+			stride: width,
+			format: PixelFormat::Rgb332,
+			damage: None,
+			origin_x: 0,
+			origin_y: 0
+		}
+	}
+
+	pub fn with_format(self, format: PixelFormat) -> Surface<'a>
+	{
+		Surface{
+			format,
+			..self
+		}
+	}
+
+	// record everything drawn from now on in damage, (origin_x, origin_y) is where
+	// the top left corner of this surface is on the screen
+	pub fn with_damage(self, damage: &'a mut DamageList, origin_x: usize, origin_y: usize) -> Surface<'a>
+	{
+		Surface{
+			damage: Some(damage),
+			origin_x,
+			origin_y,
+			..self
+		}
+	}
+
+	// window into this surface, drawing into it draws into the parent, the window gets cut to the parent bounds
+	pub fn sub_surface(&mut self, x: usize, y: usize, width: usize, height: usize) -> Surface<'_>
+	{
+		let x = usize::min(x, self.width);
+		let y = usize::min(y, self.height);
+		let start = usize::min(y * self.stride + x, self.data.len());
+
+		Surface{
+			data: &mut self.data[start..],
+			width: usize::min(width, self.width - x),
+			height: usize::min(height, self.height - y),
+			stride: self.stride,
+			format: self.format,
+			damage: self.damage.as_deref_mut(),
+			origin_x: self.origin_x + x,
+			origin_y: self.origin_y + y
+		}
+	}
+
+	pub fn index(&self, x: usize, y: usize) -> usize
+	{
+		y * self.stride + x
+	}
+
+	pub fn get_pixel(&self, x: usize, y: usize) -> u8
+	{
+		self.data[self.index(x, y)]
+	}
+
+	// every visible pixel converted to RGB 565, row by row, ready to be sent to the display
+	pub fn pixels_rgb565(&self) -> impl Iterator<Item = u16> + '_
+	{
+		(0..self.height).flat_map(move |y| {
+			let row = self.index(0, y);
+			self.data[row..row + self.width].iter().map(move |&pix| self.format.to_rgb565(pix))
+		})
+	}
+
+	pub fn clear(&mut self)
+	{
+		self.fill(0u8);
+	}
+
+	pub fn fill(&mut self, color: u8)
+	{
+		self.mark_damaged(0, 0, self.width, self.height);
 
-	for x in 0..width 
+		for y in 0..self.height
+		{
+			let row = self.index(0, y);
+
+			for pix in self.data[row..row + self.width].iter_mut()
+			{
+				*pix = color;
+			}
+		}
+	}
+
+	pub fn draw_iter<T: Iterator<Item = u8>>(&mut self, x_begin: usize, y_begin: usize, x_end: usize, y_end: usize, mut pixels: T)
 	{
-		for y in 0..height
+		self.mark_damaged(x_begin, y_begin, usize::min(x_end, self.width).saturating_sub(x_begin), usize::min(y_end, self.height).saturating_sub(y_begin));
+
+		for x in x_begin..usize::min(x_end, self.width)
 		{
-			let current_color: u8 = pixels[(y * width) + x];
-			draw_pixel(framebuffer, x + x_begin, y + y_begin, current_color);
+			for y in y_begin..usize::min(y_end, self.height)
+			{
+				match pixels.next()
+				{
+					None => { return },
+					Some(pix) => { let index = self.index(x, y); self.data[index] = pix; }
+				}
+			}
 		}
 	}
-}
 
-pub fn draw_pixel(framebuffer: &mut [u8], x: usize, y: usize, color: u8)
-{
-	if (x > 0 && x < 240) &&  (y > 0 && y < 320)
+	pub fn draw_array(&mut self, x_begin: usize, y_begin: usize, pixels: &[u8], width: usize)
 	{
-		framebuffer[y * 240 + x] = color;
+		let height = pixels.len() / width;
+
+		for x in 0..width
+		{
+			for y in 0..height
+			{
+				let current_color: u8 = pixels[(y * width) + x];
+				self.draw_pixel(x + x_begin, y + y_begin, current_color);
+			}
+		}
 	}
-}
 
-pub fn clear(framebuffer: &mut [u8])
-{
-	for x in 0..(240 * 320)
+	pub fn draw_pixel(&mut self, x: usize, y: usize, color: u8)
 	{
-		framebuffer[x] = 0u8;
+		if x < self.width && y < self.height
+		{
+			let index = self.index(x, y);
+			self.data[index] = color;
+			self.mark_damaged(x, y, 1, 1);
+		}
 	}
-}
 
-pub fn draw_line_vertex(framebuffer: &mut [u8], begin: &vec2f, end: &vec2f, color: u8)
-{
-	draw_line(framebuffer, &vec2f { x: begin.x, y: begin.y }, &vec2f { x: end.x, y: end.y }, color);
-}
+	// color is mixed into what's already there, alpha 255 is the same as draw_pixel
+	pub fn blend_pixel(&mut self, x: usize, y: usize, color: u8, alpha: u8)
+	{
+		if x < self.width && y < self.height
+		{
+			let index = self.index(x, y);
+			self.data[index] = self.format.blend(self.data[index], color, alpha);
+			self.mark_damaged(x, y, 1, 1);
+		}
+	}
 
-pub fn draw_vertex(framebuffer: &mut [u8], vert: &vec2f, pixel: u8)
-{
-	draw_pixel(framebuffer, vert.x as usize, vert.y as usize, pixel);
-}
+	// same as draw_pixel, but the pixel is only written if it passes the depth test
+	// the depth buffer is indexed the same way as the surface, stride included
+	pub fn draw_pixel_depth(&mut self, depthbuffer: &mut [u16], x: usize, y: usize, depth: u16, color: u8)
+	{
+		if x < self.width && y < self.height
+		{
+			let index = self.index(x, y);
 
-pub fn draw_line(framebuffer: &mut [u8], begin: &vec2f, end: &vec2f, color: u8)
-{
-	let line_vec: vec2f = end - begin;
-	let slope = line_vec.y / line_vec.x;
+			if depthbuffer::test_and_set(depthbuffer, index, depth)
+			{
+				self.data[index] = color;
+				self.mark_damaged(x, y, 1, 1);
+			}
+		}
+	}
 
-	if slope <= 1.0f32 && slope >= -1.0f32
+	pub fn draw_line_vertex(&mut self, begin: &vec2f, end: &vec2f, color: u8)
 	{
-		draw_line_x(framebuffer, begin, &line_vec, slope, color);
+		self.draw_line(&vec2f { x: begin.x, y: begin.y }, &vec2f { x: end.x, y: end.y }, color);
 	}
-	else 
+
+	pub fn draw_vertex(&mut self, vert: &vec2f, pixel: u8)
 	{
-		draw_line_y(framebuffer, begin, &line_vec, slope, color);
+		self.draw_pixel_signed(vert.x, vert.y, pixel);
 	}
-}
 
-pub fn draw_line_horizontal(framebuffer: &mut [u8], y: f32, begin_x: f32, end_x: f32, color: u8)
-{
-	if begin_x < end_x 
+	pub fn draw_line(&mut self, begin: &vec2f, end: &vec2f, color: u8)
 	{
-		for x in (begin_x as usize)..(end_x as usize)
+		self.draw_line_styled(begin, end, &LineStyle::solid(), color);
+	}
+
+	// float positions are snapped to the pixel they fall into, unless the line is anti-aliased
+	pub fn draw_line_styled(&mut self, begin: &vec2f, end: &vec2f, style: &LineStyle, color: u8)
+	{
+		if style.anti_aliased
 		{
-			draw_pixel(framebuffer, x, y as usize, color)
+			self.draw_line_anti_aliased(begin, end, style, color);
+			return;
 		}
+
+		self.draw_line_int(
+			libm::floorf(begin.x) as i32,
+			libm::floorf(begin.y) as i32,
+			libm::floorf(end.x) as i32,
+			libm::floorf(end.y) as i32,
+			style,
+			color);
 	}
-	else
+
+	// Bresenham between two pixels, both endpoints are drawn
+	// Only the steps of the line that are on the surface are taken, so off-screen parts cost nothing.
+	// They're found from the line itself rather than by clipping its endpoints, which keeps every
+	// pixel where it would be on a bigger surface. That matters for surfaces covering part of the
+	// screen, like bands and strips. The dash pattern keeps its place the same way.
+	pub fn draw_line_int(&mut self, x0: i32, y0: i32, x1: i32, y1: i32, style: &LineStyle, color: u8)
 	{
-		for x in (end_x as usize)..(begin_x as usize)
+		// endpoints can be anywhere in i32, the differences and the error term need i64
+		let dx = (x1 as i64 - x0 as i64).abs();
+		let dy = -(y1 as i64 - y0 as i64).abs();
+		let x_major = dx >= -dy;
+		let (brush_low, brush_high) = style.brush_range();
+
+		// leave room for the brush, pixels of a thick line can be on screen while its center isn't
+		let margin = i32::max(-brush_low, brush_high);
+		let rect = ClipRect{
+			left: -margin,
+			top: -margin,
+			right: self.width as i32 - 1 + margin,
+			bottom: self.height as i32 - 1 + margin
+		};
+
+		let step_x = if x0 < x1 { 1 } else { -1 };
+		let step_y = if y0 < y1 { 1 } else { -1 };
+
+		let (first_step, last_step) = match line::visible_steps(&rect, x0, y0, x1, y1)
+		{
+			Some(steps) => steps,
+			None => return
+		};
+
+		let (mut x, mut y, mut error) = line::bresenham_state(x0, y0, x1, y1, first_step);
+		let mut step = first_step;
+
+		loop
 		{
-			draw_pixel(framebuffer, x, y as usize, color)
+			if style.is_drawn((step % style.dash_length as i64) as u32)
+			{
+				for offset in brush_low..=brush_high
+				{
+					if x_major
+					{
+						self.draw_pixel_int(x, y + offset, color);
+					}
+					else
+					{
+						self.draw_pixel_int(x + offset, y, color);
+					}
+				}
+			}
+
+			if step == last_step
+			{
+				break;
+			}
+
+			let double_error = 2 * error;
+
+			if double_error >= dy
+			{
+				error += dy;
+				x += step_x;
+			}
+			if double_error <= dx
+			{
+				error += dx;
+				y += step_y;
+			}
+
+			step += 1;
 		}
 	}
-}
 
-fn draw_line_x(framebuffer: &mut [u8], begin: &vec2f, line_vec: &vec2f, slope: f32, color: u8)
-{
-	if line_vec.x > 0.0f32
+	// Xiaolin Wu style, every pixel the line passes gets the fraction of it that's covered as alpha.
+	// The line is treated as thickness pixels wide across its minor axis and sticks out
+	// half a pixel past both ends, so it touches the same pixels draw_line_int would.
+	pub fn draw_line_anti_aliased(&mut self, begin: &vec2f, end: &vec2f, style: &LineStyle, color: u8)
 	{
-		
-		for x in 0..(line_vec.x as i32)
+		let x_major = libm::fabsf(end.x - begin.x) >= libm::fabsf(end.y - begin.y);
+
+		// from here on everything is in (major, minor) coordinates
+		let (begin_major, begin_minor, end_major, end_minor, major_size) = if x_major
 		{
-			draw_pixel(framebuffer, (x as f32 + begin.x) as usize, ((x as f32 * slope) + begin.y) as usize, color);
+			(begin.x, begin.y, end.x, end.y, self.width)
+		}
+		else
+		{
+			(begin.y, begin.x, end.y, end.x, self.height)
+		};
+
+		let gradient = if end_major != begin_major { (end_minor - begin_minor) / (end_major - begin_major) } else { 0.0f32 };
+		let half_thickness = style.thickness as f32 * 0.5f32;
+
+		let low = f32::min(begin_major, end_major) - 0.5f32;
+		let high = f32::max(begin_major, end_major) + 0.5f32;
+
+		// only the part of the line over the surface is walked
+		let first = f32::max(libm::floorf(low), 0.0f32) as i32;
+		let last = f32::min(libm::floorf(high), major_size as f32 - 1.0f32) as i32;
+		let begin_pixel = libm::floorf(begin_major) as i32;
+
+		for major in first..=last
+		{
+			if !style.is_drawn((major - begin_pixel).unsigned_abs())
+			{
+				continue;
+			}
+
+			let major_coverage = coverage(major, low, high);
+
+			// minor position of the line's center where it crosses the middle of this pixel
+			let center = begin_minor + (major as f32 + 0.5f32 - begin_major) * gradient;
+			let top = center - half_thickness;
+			let bottom = center + half_thickness;
+
+			for minor in (libm::floorf(top) as i32)..=(libm::floorf(bottom) as i32)
+			{
+				let alpha = (major_coverage * coverage(minor, top, bottom) * 255.0f32 + 0.5f32) as u8;
+
+				if alpha == 0u8 || minor < 0
+				{
+					continue;
+				}
+
+				if x_major
+				{
+					self.blend_pixel(major as usize, minor as usize, color, alpha);
+				}
+				else
+				{
+					self.blend_pixel(minor as usize, major as usize, color, alpha);
+				}
+			}
 		}
 	}
-	else 
+
+	pub fn draw_line_horizontal(&mut self, y: f32, begin_x: f32, end_x: f32, color: u8)
 	{
-		for x in (line_vec.x as i32)..0
+		let (left_x, right_x) = if begin_x < end_x { (begin_x, end_x) } else { (end_x, begin_x) };
+
+		if let Some((row, left, right)) = self.clip_span(y, left_x, right_x)
 		{
-			draw_pixel(framebuffer, (x as f32 + begin.x) as usize, ((x as f32 * slope) + begin.y) as usize, color);
+			self.mark_damaged(left, y as usize, right - left, 1);
+
+			for pix in self.data[row + left..row + right].iter_mut()
+			{
+				*pix = color;
+			}
 		}
 	}
-}
 
-fn draw_line_y(framebuffer: &mut [u8], begin: &vec2f, line_vec: &vec2f, slope: f32, color: u8)
-{
-	if line_vec.y > 0.0f32
+	// horizontal span with the depth linearly interpolated between begin_z and end_z
+	pub fn draw_line_horizontal_depth(&mut self, depthbuffer: &mut [u16], y: f32, begin_x: f32, end_x: f32, begin_z: f32, end_z: f32, color: u8)
 	{
-		for y in 0..(line_vec.y as i32)
+		// always walk left -> right so the depth step has a single direction
+		let (left_x, right_x, left_z, right_z) = if begin_x < end_x
 		{
-			draw_pixel(framebuffer, ((y as f32 / slope) + begin.x) as usize, (y + begin.y as i32) as usize, color);
+			(begin_x, end_x, begin_z, end_z)
 		}
+		else
+		{
+			(end_x, begin_x, end_z, begin_z)
+		};
+
+		let width = right_x - left_x;
+		let z_step = if width > 0.0f32 { (right_z - left_z) / width } else { 0.0f32 };
+
+		if let Some((row, left, right)) = self.clip_span(y, left_x, right_x)
+		{
+			// the whole span counts, even the parts that end up failing the depth test
+			self.mark_damaged(left, y as usize, right - left, 1);
+
+			for x in left..right
+			{
+				let z = left_z + (x as f32 - left_x) * z_step;
+
+				if depthbuffer::test_and_set(depthbuffer, row + x, depthbuffer::to_depth(z))
+				{
+					self.data[row + x] = color;
+				}
+			}
+		}
+	}
+
+	// record a rectangle of this surface as drawn to, does nothing without a damage list
+	pub fn mark_damaged(&mut self, x: usize, y: usize, width: usize, height: usize)
+	{
+		if let Some(damage) = self.damage.as_deref_mut()
+		{
+			if let Some(rect) = DamageRect::new(self.origin_x + x, self.origin_y + y, width, height)
+			{
+				damage.add(rect);
+			}
+		}
+	}
+
+	// set the pixels covered by the rectangles (in screen coordinates, like the ones recorded)
+	// to color, without recording them as damage. This is how the previous frame gets erased.
+	pub fn fill_damage(&mut self, damage: &DamageList, color: u8)
+	{
+		for rect in damage.rects()
+		{
+			let left = usize::min(rect.left.saturating_sub(self.origin_x), self.width);
+			let right = usize::min((rect.right + 1).saturating_sub(self.origin_x), self.width);
+			let top = usize::min(rect.top.saturating_sub(self.origin_y), self.height);
+			let bottom = usize::min((rect.bottom + 1).saturating_sub(self.origin_y), self.height);
+
+			for y in top..bottom
+			{
+				let row = self.index(0, y);
+
+				for pix in self.data[row + left..row + right].iter_mut()
+				{
+					*pix = color;
+				}
+			}
+		}
+	}
+
+	// cut a span to the surface, returns the row start and the [left, right) pixel range
+	pub fn clip_span(&self, y: f32, left_x: f32, right_x: f32) -> Option<(usize, usize, usize)>
+	{
+		if y < 0.0f32 || y >= self.height as f32
+		{
+			return None;
+		}
+
+		let left = f32::max(0.0f32, left_x) as usize;
+		let right = f32::min(self.width as f32, right_x) as usize;
+
+		if left >= right
+		{
+			return None;
+		}
+
+		Some((self.index(0, y as usize), left, right))
 	}
-	else 
+
+	// float coordinates can be negative, those must not wrap around to the other side
+	fn draw_pixel_signed(&mut self, x: f32, y: f32, color: u8)
 	{
-		for y in (line_vec.y as i32)..0
+		if x >= 0.0f32 && y >= 0.0f32
 		{
-			draw_pixel(framebuffer, ((y as f32 / slope) + begin.x) as usize, (y + begin.y as i32) as usize, color);
+			self.draw_pixel(x as usize, y as usize, color);
 		}
 	}
-}
\ No newline at end of file
+
+	fn draw_pixel_int(&mut self, x: i32, y: i32, color: u8)
+	{
+		if x >= 0 && y >= 0
+		{
+			self.draw_pixel(x as usize, y as usize, color);
+		}
+	}
+}
+
+// how much of the pixel [pixel, pixel + 1) lies inside [low, high]
+fn coverage(pixel: i32, low: f32, high: f32) -> f32
+{
+	let pixel = pixel as f32;
+	f32::max(0.0f32, f32::min(pixel + 1.0f32, high) - f32::max(pixel, low))
+}
@@ -0,0 +1,161 @@
+use core::convert::Infallible;
+use embedded_graphics::pixelcolor::{PixelColor, RgbColor, BinaryColor, Rgb565, Rgb888};
+use embedded_graphics::pixelcolor::raw::{RawData, RawU8, RawU16};
+use embedded_graphics::prelude::{DrawTarget, OriginDimensions, Pixel, Size};
+use embedded_graphics::primitives::Rectangle;
+use crate::framebuffer::Surface;
+use crate::pixel_ops::{make_pixel, split_pixel, expand_rgb565};
+
+// embedded-graphics support, so its fonts, images and widgets can be drawn into the same
+// Surface as the 3D renderer. Colors are the framebuffer's RGB 332 bytes, nothing gets converted
+// on the way in for Rgb332 surfaces.
+
+// 3 bits red, 3 bits green, 2 bits blue, same layout as pixel_ops::make_pixel
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct Rgb332(pub u8);
+
+impl Rgb332
+{
+	// r and g go up to 7, b up to 3
+	pub const fn new(r: u8, g: u8, b: u8) -> Rgb332
+	{
+		Rgb332(((r & 0b111) << 5) | ((g & 0b111) << 2) | (b & 0b11))
+	}
+}
+
+impl PixelColor for Rgb332
+{
+	type Raw = RawU8;
+}
+
+impl RgbColor for Rgb332
+{
+	fn r(&self) -> u8
+	{
+		self.0 >> 5
+	}
+
+	fn g(&self) -> u8
+	{
+		(self.0 >> 2) & 0b111
+	}
+
+	fn b(&self) -> u8
+	{
+		self.0 & 0b11
+	}
+
+	const MAX_R: u8 = 7;
+	const MAX_G: u8 = 7;
+	const MAX_B: u8 = 3;
+
+	const BLACK: Self = Rgb332::new(0, 0, 0);
+	const RED: Self = Rgb332::new(7, 0, 0);
+	const GREEN: Self = Rgb332::new(0, 7, 0);
+	const BLUE: Self = Rgb332::new(0, 0, 3);
+	const YELLOW: Self = Rgb332::new(7, 7, 0);
+	const MAGENTA: Self = Rgb332::new(7, 0, 3);
+	const CYAN: Self = Rgb332::new(0, 7, 3);
+	const WHITE: Self = Rgb332::new(7, 7, 3);
+}
+
+impl From<RawU8> for Rgb332
+{
+	fn from(raw: RawU8) -> Self
+	{
+		Rgb332(raw.into_inner())
+	}
+}
+
+impl From<Rgb332> for RawU8
+{
+	fn from(color: Rgb332) -> Self
+	{
+		RawU8::new(color.0)
+	}
+}
+
+impl From<Rgb888> for Rgb332
+{
+	fn from(color: Rgb888) -> Self
+	{
+		Rgb332(make_pixel(color.r(), color.g(), color.b()))
+	}
+}
+
+impl From<Rgb565> for Rgb332
+{
+	fn from(color: Rgb565) -> Self
+	{
+		let (r, g, b) = expand_rgb565(RawU16::from(color).into_inner());
+		Rgb332(make_pixel(r, g, b))
+	}
+}
+
+impl From<BinaryColor> for Rgb332
+{
+	fn from(color: BinaryColor) -> Self
+	{
+		match color
+		{
+			BinaryColor::Off => Rgb332::BLACK,
+			BinaryColor::On => Rgb332::WHITE
+		}
+	}
+}
+
+impl From<Rgb332> for Rgb888
+{
+	fn from(color: Rgb332) -> Self
+	{
+		let (r, g, b) = split_pixel(color.0);
+		Rgb888::new(r, g, b)
+	}
+}
+
+impl<'a> OriginDimensions for Surface<'a>
+{
+	fn size(&self) -> Size
+	{
+		Size::new(self.width as u32, self.height as u32)
+	}
+}
+
+impl<'a> DrawTarget for Surface<'a>
+{
+	type Color = Rgb332;
+	type Error = Infallible;
+
+	fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+	where
+		I: IntoIterator<Item = Pixel<Self::Color>>
+	{
+		for Pixel(point, color) in pixels
+		{
+			if point.x >= 0 && point.y >= 0
+			{
+				let pix = self.format.from_rgb332(color.0);
+				self.draw_pixel(point.x as usize, point.y as usize, pix);
+			}
+		}
+
+		Ok(())
+	}
+
+	// solid rectangles go through the span based fill instead of pixel by pixel
+	fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error>
+	{
+		let pix = self.format.from_rgb332(color.0);
+		self.fill_rect(area.top_left.x, area.top_left.y, area.size.width as i32, area.size.height as i32, pix);
+
+		Ok(())
+	}
+
+	fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error>
+	{
+		let pix = self.format.from_rgb332(color.0);
+		self.fill(pix);
+
+		Ok(())
+	}
+}
@@ -0,0 +1,267 @@
+use crate::vector::vec2f;
+use crate::framebuffer::Surface;
+use crate::font::{Font, draw_text};
+
+// Multi line text on top of any Font: measuring, alignment, '\n' and word wrap.
+// Lines are broken at '\n', and when a wrap width is set also at the last space that keeps
+// the line inside it. Words longer than the wrap width are broken wherever they hit the edge.
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Align
+{
+	Left,
+	Center,
+	Right
+}
+
+// pixel rectangle, x and y are the top left corner
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct TextRect
+{
+	pub x: f32,
+	pub y: f32,
+	pub width: f32,
+	pub height: f32
+}
+
+#[derive(Clone, Copy)]
+pub struct TextLayout
+{
+	pub align: Align,
+	// extra pixels between the bottom of one line and the top of the next
+	pub line_spacing: f32,
+	// With a wrap width the text is laid out in a box that wide, starting at the position, and
+	// every line is aligned inside it. Without one the position is the left end, center or
+	// right end of every line, depending on align.
+	pub wrap_width: Option<f32>,
+	// lines that would reach below this are left out
+	pub max_height: Option<f32>
+}
+
+impl TextLayout
+{
+	pub fn new() -> TextLayout
+	{
+		TextLayout{
+			align: Align::Left,
+			line_spacing: 0.0f32,
+			wrap_width: None,
+			max_height: None
+		}
+	}
+
+	// wrap and align inside the rectangle, whatever doesn't fit below it is dropped
+	pub fn in_rect(width: f32, height: f32) -> TextLayout
+	{
+		TextLayout::new().with_wrap_width(Some(width)).with_max_height(Some(height))
+	}
+
+	pub fn with_align(&self, align: Align) -> TextLayout
+	{
+		TextLayout{
+			align,
+			..*self
+		}
+	}
+
+	pub fn with_line_spacing(&self, line_spacing: f32) -> TextLayout
+	{
+		TextLayout{
+			line_spacing,
+			..*self
+		}
+	}
+
+	pub fn with_wrap_width(&self, wrap_width: Option<f32>) -> TextLayout
+	{
+		TextLayout{
+			wrap_width,
+			..*self
+		}
+	}
+
+	pub fn with_max_height(&self, max_height: Option<f32>) -> TextLayout
+	{
+		TextLayout{
+			max_height,
+			..*self
+		}
+	}
+
+	// x of the left end of a line
+	fn line_x(&self, position_x: f32, line_width: f32) -> f32
+	{
+		let box_width = self.wrap_width.unwrap_or(0.0f32);
+
+		match self.align
+		{
+			Align::Left => position_x,
+			Align::Center => position_x + (box_width - line_width) * 0.5f32,
+			Align::Right => position_x + box_width - line_width
+		}
+	}
+}
+
+impl Default for TextLayout
+{
+	fn default() -> Self {
+		TextLayout::new()
+	}
+}
+
+// the lines text gets broken into, with their widths in pixels
+pub fn text_lines<'a, F: Font>(font: &'a F, layout: &TextLayout, text: &'a [u8]) -> TextLines<'a, F>
+{
+	TextLines{
+		font,
+		wrap_width: layout.wrap_width,
+		text,
+		start: 0,
+		done: false
+	}
+}
+
+pub struct TextLines<'a, F: Font>
+{
+	font: &'a F,
+	wrap_width: Option<f32>,
+	text: &'a [u8],
+	start: usize,
+	done: bool
+}
+
+impl<'a, F: Font> TextLines<'a, F>
+{
+	fn line_width(&self, line: &[u8]) -> f32
+	{
+		line.iter().map(|char| self.font.advance(*char)).sum()
+	}
+
+	// the line from start to end, without the spaces a wrap leaves at its end
+	fn wrapped_line(&mut self, end: usize, next_start: usize) -> (&'a [u8], f32)
+	{
+		let mut line = &self.text[self.start..end];
+
+		while let [rest @ .., b' '] = line
+		{
+			line = rest;
+		}
+
+		// the next line doesn't start with the spaces the wrap happened at either
+		let mut next_start = next_start;
+		while next_start < self.text.len() && self.text[next_start] == b' '
+		{
+			next_start += 1;
+		}
+
+		self.start = next_start;
+		(line, self.line_width(line))
+	}
+}
+
+impl<'a, F: Font> Iterator for TextLines<'a, F>
+{
+	type Item = (&'a [u8], f32);
+
+	fn next(&mut self) -> Option<(&'a [u8], f32)>
+	{
+		if self.done
+		{
+			return None;
+		}
+
+		let mut width = 0.0f32;
+		// index of the last space, where the line can be wrapped
+		let mut last_space: Option<usize> = None;
+
+		for i in self.start..self.text.len()
+		{
+			let char = self.text[i];
+
+			if char == b'\n'
+			{
+				let line = &self.text[self.start..i];
+				self.start = i + 1;
+				return Some((line, width));
+			}
+
+			let advance = self.font.advance(char);
+
+			if let Some(wrap_width) = self.wrap_width
+			{
+				if char != b' ' && width + advance > wrap_width
+				{
+					match last_space
+					{
+						Some(space) => return Some(self.wrapped_line(space, space + 1)),
+						// a word too long for a line of its own, at least one char goes on every line
+						None if i > self.start => return Some(self.wrapped_line(i, i)),
+						None => ()
+					}
+				}
+			}
+
+			if char == b' '
+			{
+				last_space = Some(i);
+			}
+
+			width += advance;
+		}
+
+		self.done = true;
+		Some((&self.text[self.start..], width))
+	}
+}
+
+// where the text would go, without drawing it
+pub fn measure_text<F: Font>(font: &F, layout: &TextLayout, text: &[u8], position: vec2f) -> TextRect
+{
+	layout_text(font, layout, text, position, |_, _| ())
+}
+
+// position is the top left corner of the text when it's left aligned, see TextLayout for the
+// others. Returns the bounding box of the lines that were drawn.
+pub fn draw_text_layout<F: Font>(framebuffer: &mut Surface, font: &F, layout: &TextLayout, text: &[u8], position: vec2f, color: u8) -> TextRect
+{
+	layout_text(font, layout, text, position, |line, line_position| draw_text(framebuffer, font, line, line_position, color))
+}
+
+fn layout_text<F: Font, D: FnMut(&[u8], vec2f)>(font: &F, layout: &TextLayout, text: &[u8], position: vec2f, mut draw_line: D) -> TextRect
+{
+	let line_height = font.line_height();
+	let mut left = f32::MAX;
+	let mut right = f32::MIN;
+	let mut y = position.y;
+
+	for (line, width) in text_lines(font, layout, text)
+	{
+		if let Some(max_height) = layout.max_height
+		{
+			if y + line_height > position.y + max_height
+			{
+				break;
+			}
+		}
+
+		let x = layout.line_x(position.x, width);
+		draw_line(line, vec2f{x, y});
+
+		left = f32::min(left, x);
+		right = f32::max(right, x + width);
+		y += line_height + layout.line_spacing;
+	}
+
+	// nothing fit
+	if left > right
+	{
+		return TextRect{x: position.x, y: position.y, width: 0.0f32, height: 0.0f32};
+	}
+
+	TextRect{
+		x: left,
+		y: position.y,
+		width: right - left,
+		height: y - layout.line_spacing - position.y
+	}
+}
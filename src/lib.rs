@@ -0,0 +1,37 @@
+#![no_std]
+// the math types are named after their shader counterparts (vec3f, mat4x4, ...)
+#![allow(non_camel_case_types)]
+// the rasterizers take the whole pipeline state (buffers, matrices, viewport, light) as arguments
+#![allow(clippy::too_many_arguments)]
+
+// Everything in here is hardware independent. The RP2040 firmware (firmware/)
+// and the host simulator (sim/) both render through it.
+
+pub mod pixel_ops;
+pub mod framebuffer;
+pub mod damage;
+pub mod depthbuffer;
+pub mod model;
+pub mod vector;
+pub mod text;
+pub mod matrix;
+pub mod light;
+pub mod texture;
+pub mod clip;
+pub mod line;
+pub mod shapes;
+pub mod graphics;
+pub mod font;
+pub mod bitmap_font;
+pub mod layout;
+pub mod console;
+pub mod viewport;
+pub mod scene;
+pub mod transfer;
+pub mod pipeline;
+pub mod tiles;
+pub mod st7789;
+
+// the panel is used in portrait, framebuffer rows are SCREEN_WIDTH pixels long
+pub const SCREEN_WIDTH: usize = 240usize;
+pub const SCREEN_HEIGHT: usize = 320usize;
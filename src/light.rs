@@ -0,0 +1,42 @@
+use crate::vector::vec3f;
+use crate::pixel_ops::{make_pixel, split_pixel};
+
+// Directional light, the direction is the way the light travels in world space.
+// ambient is the intensity a face gets even when it points away from the light.
+#[derive(Clone, Copy)]
+pub struct DirectionalLight
+{
+	pub direction: vec3f,
+	pub ambient: f32
+}
+
+impl DirectionalLight
+{
+	pub fn new(direction: vec3f, ambient: f32) -> DirectionalLight
+	{
+		DirectionalLight{
+			direction: direction.normalize(),
+			ambient
+		}
+	}
+
+	// lambert term for a normalized world space normal, in the [ambient, 1] range
+	pub fn intensity(&self, normal: &vec3f) -> f32
+	{
+		let diffuse = f32::max(0.0f32, -normal.dot(&self.direction));
+
+		self.ambient + (1.0f32 - self.ambient) * diffuse
+	}
+}
+
+// scale every channel of an RGB 332 color and quantise it back to RGB 332
+pub fn shade_pixel(color: u8, intensity: f32) -> u8
+{
+	let (r, g, b) = split_pixel(color);
+	let intensity = intensity.clamp(0.0f32, 1.0f32);
+
+	make_pixel(
+		(r as f32 * intensity) as u8,
+		(g as f32 * intensity) as u8,
+		(b as f32 * intensity) as u8)
+}
@@ -0,0 +1,177 @@
+// Line helpers, the drawing itself lives in Surface::draw_line_int and Surface::draw_line_anti_aliased.
+// Lines are walked one pixel per step along their major axis, dash patterns
+// and brush thickness are both counted in those steps.
+
+// how a line puts its pixels down
+#[derive(Clone, Copy, PartialEq)]
+pub struct LineStyle
+{
+	// pixels across the line, measured along the minor axis
+	pub thickness: u8,
+	// bit i tells whether step i of every dash_length long period is drawn, lowest bit first
+	pub dash_pattern: u32,
+	pub dash_length: u8,
+	// blend the covered fraction of every pixel instead of setting whole pixels
+	pub anti_aliased: bool
+}
+
+impl LineStyle
+{
+	pub fn solid() -> LineStyle
+	{
+		LineStyle{
+			thickness: 1u8,
+			dash_pattern: 1u32,
+			dash_length: 1u8,
+			anti_aliased: false
+		}
+	}
+
+	pub fn with_thickness(&self, thickness: u8) -> LineStyle
+	{
+		LineStyle{
+			thickness: u8::max(1u8, thickness),
+			..*self
+		}
+	}
+
+	// length is clamped to [1, 32], 0b0011 with a length of 4 gives 2 pixels on, 2 off
+	pub fn with_dash(&self, dash_pattern: u32, dash_length: u8) -> LineStyle
+	{
+		LineStyle{
+			dash_pattern,
+			dash_length: dash_length.clamp(1u8, 32u8),
+			..*self
+		}
+	}
+
+	pub fn with_anti_aliasing(&self, anti_aliased: bool) -> LineStyle
+	{
+		LineStyle{
+			anti_aliased,
+			..*self
+		}
+	}
+
+	pub fn is_drawn(&self, step: u32) -> bool
+	{
+		(self.dash_pattern >> (step % self.dash_length as u32)) & 1u32 != 0u32
+	}
+
+	// the brush covers [low, high] around the line along the minor axis
+	pub fn brush_range(&self) -> (i32, i32)
+	{
+		let low = -((self.thickness as i32 - 1) / 2);
+		(low, low + self.thickness as i32 - 1)
+	}
+}
+
+impl Default for LineStyle
+{
+	fn default() -> Self {
+		LineStyle::solid()
+	}
+}
+
+// inclusive pixel bounds
+#[derive(Clone, Copy)]
+pub struct ClipRect
+{
+	pub left: i32,
+	pub top: i32,
+	pub right: i32,
+	pub bottom: i32
+}
+
+// Surface::draw_line_int steps along the major axis of a line (the one it covers more pixels of)
+// one pixel at a time and moves along the minor axis whenever the error term says so. After n
+// steps the line has moved floor((major + 2 * n * minor) / (2 * major)) pixels along the minor
+// axis, major and minor being the line's size along them. That's all that's needed to jump into
+// the middle of the line, or to find where it's on a surface, without walking up to there.
+
+// the line from (x0, y0) to (x1, y1) as (major, minor) sizes and whether it's x major
+fn line_axes(x0: i32, y0: i32, x1: i32, y1: i32) -> (i64, i64, bool)
+{
+	let (width, height) = ((x1 as i64 - x0 as i64).abs(), (y1 as i64 - y0 as i64).abs());
+
+	if width >= height { (width, height, true) } else { (height, width, false) }
+}
+
+// lines can span the whole i32 range, the products below need more than 64 bits for those
+fn minor_steps(major: i64, minor: i64, step: i64) -> i64
+{
+	let (major, minor, step) = (major as i128, minor as i128, step as i128);
+
+	if major == 0 { 0 } else { (major + 2 * step * minor).div_euclid(2 * major) as i64 }
+}
+
+// the first step that has moved at least moves pixels along the minor axis
+fn first_step_with(major: i64, minor: i64, moves: i64) -> Option<i64>
+{
+	if moves <= 0
+	{
+		return Some(0);
+	}
+
+	if minor == 0
+	{
+		return None;
+	}
+
+	// smallest step with major + 2 * step * minor >= 2 * major * moves
+	let (major, minor, moves) = (major as i128, minor as i128, moves as i128);
+	Some((2 * major * moves - major + 2 * minor - 1).div_euclid(2 * minor) as i64)
+}
+
+// position and error term of draw_line_int's Bresenham after step steps
+pub fn bresenham_state(x0: i32, y0: i32, x1: i32, y1: i32, step: i64) -> (i32, i32, i64)
+{
+	let (major, minor, x_major) = line_axes(x0, y0, x1, y1);
+	let step_x = if x0 < x1 { 1i64 } else { -1i64 };
+	let step_y = if y0 < y1 { 1i64 } else { -1i64 };
+	let moves = minor_steps(major, minor, step);
+	let (major_wide, minor_wide, step_wide, moves_wide) = (major as i128, minor as i128, step as i128, moves as i128);
+
+	// error = dx + dy, plus dy for every x step and dx for every y step (dy being negative)
+	if x_major
+	{
+		((x0 as i64 + step * step_x) as i32, (y0 as i64 + moves * step_y) as i32, (major_wide - minor_wide - step_wide * minor_wide + moves_wide * major_wide) as i64)
+	}
+	else
+	{
+		((x0 as i64 + moves * step_x) as i32, (y0 as i64 + step * step_y) as i32, (minor_wide - major_wide + step_wide * minor_wide - moves_wide * major_wide) as i64)
+	}
+}
+
+// first and last step of the line from (x0, y0) to (x1, y1) that are inside rect, None if it misses it
+pub fn visible_steps(rect: &ClipRect, x0: i32, y0: i32, x1: i32, y1: i32) -> Option<(i64, i64)>
+{
+	let (major, minor, x_major) = line_axes(x0, y0, x1, y1);
+
+	// how far the line has to move along an axis to get into [low, high], and how far until it's through
+	let range = |begin: i32, end: i32, low: i32, high: i32| -> (i64, i64) {
+		if begin <= end
+		{
+			(low as i64 - begin as i64, high as i64 - begin as i64)
+		}
+		else
+		{
+			(begin as i64 - high as i64, begin as i64 - low as i64)
+		}
+	};
+
+	let (x_range, y_range) = (range(x0, x1, rect.left, rect.right), range(y0, y1, rect.top, rect.bottom));
+	let (major_range, minor_range) = if x_major { (x_range, y_range) } else { (y_range, x_range) };
+
+	// the major axis moves by one every step
+	let mut first = i64::max(0, major_range.0);
+	let mut last = i64::min(major, major_range.1);
+
+	first = i64::max(first, first_step_with(major, minor, minor_range.0)?);
+	if let Some(past) = first_step_with(major, minor, minor_range.1 + 1)
+	{
+		last = i64::min(last, past - 1);
+	}
+
+	if first <= last { Some((first, last)) } else { None }
+}
@@ -1,5 +1,5 @@
-use core::ops::{self, Mul};
-use crate::vector::{vec3f, vec4f};
+use core::ops;
+use crate::vector::{vec2f, vec3f, vec4f};
 use libm;
 
 #[derive(Clone, Copy)]
@@ -26,12 +26,13 @@ impl mat4x4
 	}
 
 	// some mathemagic I stole from the interwebz
-	pub fn new_projection(fov: f32, near: f32, far: f32) -> mat4x4
+	// fov is vertical, aspect_ratio is width / height of the viewport the result gets drawn into
+	pub fn new_projection(fov: f32, aspect_ratio: f32, near: f32, far: f32) -> mat4x4
 	{
 		let mut out_mat = mat4x4::new();
 
-		let scale: f32 = 1.0f32 / libm::tanf(fov * 0.5f32 * 3.14159f32 / 180.0f32);
-		out_mat.data[0][0] = scale;
+		let scale: f32 = 1.0f32 / libm::tanf(fov * 0.5f32 * core::f32::consts::PI / 180.0f32);
+		out_mat.data[0][0] = scale / aspect_ratio;
 		out_mat.data[1][1] = scale;
 		out_mat.data[2][2] = -far / (far - near);
 		out_mat.data[3][2] = -far * near / (far - near);
@@ -80,6 +81,23 @@ impl mat4x4
 
 		out_mat
 	}
+
+	// rotate/scale a direction, translation and the perspective divide are skipped
+	pub fn transform_direction(&self, vec: &vec3f) -> vec3f
+	{
+		vec3f{
+			x: (self.data[0][0] * vec.x) + (self.data[1][0] * vec.y) + (self.data[2][0] * vec.z),
+			y: (self.data[0][1] * vec.x) + (self.data[1][1] * vec.y) + (self.data[2][1] * vec.z),
+			z: (self.data[0][2] * vec.x) + (self.data[1][2] * vec.y) + (self.data[2][2] * vec.z)
+		}
+	}
+}
+
+impl Default for mat4x4
+{
+	fn default() -> Self {
+		mat4x4::new()
+	}
 }
 
 impl ops::Mul<&vec3f> for mat4x4 
@@ -108,6 +126,22 @@ impl ops::Mul<&vec3f> for mat4x4
     }
 }
 
+// same as the vec3f version but without the perspective divide, the result is in clip space
+impl ops::Mul<&vec4f> for mat4x4 
+{
+    type Output = vec4f;
+
+    fn mul(self, vec: &vec4f) -> vec4f 
+	{
+        vec4f{
+			x: (self.data[0][0] * vec.x) + (self.data[1][0] * vec.y) + (self.data[2][0] * vec.z) + (self.data[3][0] * vec.w),
+			y: (self.data[0][1] * vec.x) + (self.data[1][1] * vec.y) + (self.data[2][1] * vec.z) + (self.data[3][1] * vec.w),
+			z: (self.data[0][2] * vec.x) + (self.data[1][2] * vec.y) + (self.data[2][2] * vec.z) + (self.data[3][2] * vec.w),
+			w: (self.data[0][3] * vec.x) + (self.data[1][3] * vec.y) + (self.data[2][3] * vec.z) + (self.data[3][3] * vec.w)
+		}
+    }
+}
+
 impl ops::Mul<&mat4x4> for mat4x4 
 {
     type Output = mat4x4;
@@ -139,4 +173,113 @@ impl ops::Mul<&mat4x4> for mat4x4
 
 		out_mat
     }
-}
\ No newline at end of file
+}
+
+// 2D affine transform in homogeneous coordinates, laid out like mat4x4:
+// data[column][row], the translation is in data[2]
+#[derive(Clone, Copy)]
+pub struct mat3x3
+{
+	pub data: [[f32; 3]; 3]
+}
+
+impl mat3x3
+{
+	// identity matrix
+	pub fn new() -> mat3x3
+	{
+		mat3x3{
+			data: [
+				[1.0f32, 0.0f32, 0.0f32],
+				[0.0f32, 1.0f32, 0.0f32],
+				[0.0f32, 0.0f32, 1.0f32]
+			]
+		}
+	}
+
+	pub fn new_translation(offset: vec2f) -> mat3x3
+	{
+		let mut out_mat = mat3x3::new();
+
+		out_mat.data[2][0] = offset.x;
+		out_mat.data[2][1] = offset.y;
+
+		out_mat
+	}
+
+	// angle is in radians, with y pointing down on screen positive angles turn clockwise
+	// same as vec2f::rotate
+	pub fn new_rotation(angle: f32) -> mat3x3
+	{
+		let mut out_mat = mat3x3::new();
+
+		out_mat.data[0][0] = libm::cosf(angle);
+		out_mat.data[1][1] = libm::cosf(angle);
+		out_mat.data[0][1] = libm::sinf(angle);
+		out_mat.data[1][0] = -libm::sinf(angle);
+
+		out_mat
+	}
+
+	pub fn new_scale(x: f32, y: f32) -> mat3x3
+	{
+		let mut out_mat = mat3x3::new();
+
+		out_mat.data[0][0] = x;
+		out_mat.data[1][1] = y;
+
+		out_mat
+	}
+
+	// x moves by x * y of the point, y by y * x
+	pub fn new_shear(x: f32, y: f32) -> mat3x3
+	{
+		let mut out_mat = mat3x3::new();
+
+		out_mat.data[1][0] = x;
+		out_mat.data[0][1] = y;
+
+		out_mat
+	}
+}
+
+impl Default for mat3x3
+{
+	fn default() -> Self {
+		mat3x3::new()
+	}
+}
+
+impl ops::Mul<&vec2f> for mat3x3
+{
+	type Output = vec2f;
+
+	fn mul(self, vec: &vec2f) -> vec2f
+	{
+		vec2f{
+			x: (self.data[0][0] * vec.x) + (self.data[1][0] * vec.y) + self.data[2][0],
+			y: (self.data[0][1] * vec.x) + (self.data[1][1] * vec.y) + self.data[2][1]
+		}
+	}
+}
+
+// applies mat_in first, then self
+impl ops::Mul<&mat3x3> for mat3x3
+{
+	type Output = mat3x3;
+
+	fn mul(self, mat_in: &mat3x3) -> mat3x3
+	{
+		let mut out_mat = mat3x3::new();
+
+		for column in 0..3
+		{
+			for row in 0..3
+			{
+				out_mat.data[column][row] = (self.data[0][row] * mat_in.data[column][0]) + (self.data[1][row] * mat_in.data[column][1]) + (self.data[2][row] * mat_in.data[column][2]);
+			}
+		}
+
+		out_mat
+	}
+}
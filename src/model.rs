@@ -1,139 +1,514 @@
-use crate::vector::{vec2f, vec3f};
-use crate::framebuffer::{self, draw_line};
-use crate::pixel_ops::colors;
+use crate::vector::{vec2f, vec3f, vec4f};
+use crate::clip::{self, ClipVertex};
+use crate::framebuffer::Surface;
+use crate::line::LineStyle;
 use crate::matrix::mat4x4;
-use crate::display;
+use crate::viewport::Viewport;
+use crate::light::{DirectionalLight, shade_pixel};
+use crate::depthbuffer;
+use crate::pixel_ops::{make_pixel, make_pixel_dithered, split_pixel};
+use crate::texture::Texture;
 
 pub struct triangle
 {
 	pub points: [vec2f; 3]
 }
 
+#[derive(Clone, Copy)]
 pub struct triangle3d
 {
 	pub points: [vec3f; 3]
 }
 
-pub struct cube
+// screen space vertex, everything besides the position is interpolated across the triangle
+// color channels are kept as 0 - 255 floats and only quantised to RGB 332 per pixel
+// uv is stored divided by w and inv_w is 1/w, both are linear in screen space which keeps textures perspective correct
+#[derive(Clone, Copy)]
+pub struct Vertex
 {
-	pub points: [vec3f; 8]
+	pub position: vec3f,
+	pub color: vec3f,
+	pub uv: vec2f,
+	pub inv_w: f32
 }
 
-impl cube
+#[derive(Clone, Copy)]
+pub struct shaded_triangle
 {
-	pub fn new() -> cube
-	{	
-		cube{
-			points: [
-				vec3f{x: 1.0f32, y: 1.0f32, z: 1.0f32},
-				vec3f{x: 1.0f32, y: -1.0f32, z: 1.0f32},
-				vec3f{x: -1.0f32, y: -1.0f32, z: 1.0f32},
-				vec3f{x: -1.0f32, y: 1.0f32, z: 1.0f32},
-				vec3f{x: 1.0f32, y: 1.0f32, z: -1.0f32},
-				vec3f{x: 1.0f32, y: -1.0f32, z: -1.0f32},
-				vec3f{x: -1.0f32, y: -1.0f32, z: -1.0f32},
-				vec3f{x: -1.0f32, y: 1.0f32, z: -1.0f32}
-			]
+	pub points: [Vertex; 3]
+}
+
+// which side of a triangle gets skipped while rasterizing, front faces are counter-clockwise on screen
+#[derive(Clone, Copy, PartialEq)]
+pub enum Culling
+{
+	None,
+	Back,
+	Front
+}
+
+// Meshes reference static vertex and index data so they cost nothing to copy around.
+// Scale and translation are stored with the mesh and applied to every vertex when it gets projected.
+#[derive(Clone, Copy)]
+pub struct Mesh
+{
+	pub vertices: &'static [vec3f],
+	pub triangles: &'static [[u16; 3]],
+	pub edges: &'static [[u16; 2]],
+	pub scale: f32,
+	pub translation: vec3f,
+	pub culling: Culling,
+	pub vertex_colors: &'static [u8],
+	pub uvs: &'static [vec2f]
+}
+
+// Where the screen space triangles of a mesh go. rasterize_* draw them right away,
+// bin_* hand them to something else, like the strip bins of the tiled renderer.
+pub trait TriangleSink
+{
+	fn flat(&mut self, triangle: &triangle3d, color: u8);
+	fn shaded(&mut self, triangle: &shaded_triangle, texture: Option<&Texture>, dither: bool);
+}
+
+// draws every triangle it gets
+struct DrawTriangles<'f, 's>
+{
+	framebuffer: &'f mut Surface<'s>,
+	depthbuffer: &'f mut [u16]
+}
+
+impl<'f, 's> TriangleSink for DrawTriangles<'f, 's>
+{
+	fn flat(&mut self, triangle: &triangle3d, color: u8)
+	{
+		triangle.draw(self.framebuffer, self.depthbuffer, color);
+	}
+
+	fn shaded(&mut self, triangle: &shaded_triangle, texture: Option<&Texture>, dither: bool)
+	{
+		triangle.draw(self.framebuffer, self.depthbuffer, texture, dither);
+	}
+}
+
+// upper limit for the per vertex buffers that live on the stack while rasterizing
+// rasterize_smooth and rasterize_textured keep one of them around next to the frame and depth buffers
+// Mesh::new and the with_* builders panic on meshes that wouldn't fit or index past their vertices,
+// so a mesh that got built can always be drawn
+pub const MAX_MESH_VERTICES: usize = 128;
+
+impl Mesh
+{
+	pub fn new(vertices: &'static [vec3f], triangles: &'static [[u16; 3]], edges: &'static [[u16; 2]]) -> Mesh
+	{
+		assert!(vertices.len() <= MAX_MESH_VERTICES, "a mesh has at most MAX_MESH_VERTICES vertices");
+		assert!(triangles.iter().flatten().chain(edges.iter().flatten()).all(|&index| (index as usize) < vertices.len()),
+			"mesh indices have to point at one of its vertices");
+
+		Mesh{
+			vertices,
+			triangles,
+			edges,
+			scale: 1.0f32,
+			translation: vec3f::new(),
+			culling: Culling::None,
+			vertex_colors: &[],
+			uvs: &[]
 		}
 	}
 
-	pub fn scale(&self, factor: f32) -> cube
-	{	
-		cube{
-			points: [
-				self.points[0].scale(factor),
-				self.points[1].scale(factor),
-				self.points[2].scale(factor),
-				self.points[3].scale(factor),
-				self.points[4].scale(factor),
-				self.points[5].scale(factor),
-				self.points[6].scale(factor),
-				self.points[7].scale(factor),
-			]
+	// scaling after a translation scales the translation too, same as scaling the points directly
+	pub fn scale(&self, factor: f32) -> Mesh
+	{
+		Mesh{
+			scale: self.scale * factor,
+			translation: self.translation.scale(factor),
+			..*self
 		}
 	}
 
-	pub fn translate(&self, trans_vec: vec3f) -> cube
-	{	
-		cube{
-			points: [
-				self.points[0] + &trans_vec,
-				self.points[1] + &trans_vec,
-				self.points[2] + &trans_vec,
-				self.points[3] + &trans_vec,
-				self.points[4] + &trans_vec,
-				self.points[5] + &trans_vec,
-				self.points[6] + &trans_vec,
-				self.points[7] + &trans_vec,
-			]
+	pub fn translate(&self, trans_vec: vec3f) -> Mesh
+	{
+		Mesh{
+			translation: self.translation + &trans_vec,
+			..*self
+		}
+	}
+
+	// triangles of the mesh need a consistent winding for anything other than Culling::None
+	pub fn with_culling(&self, culling: Culling) -> Mesh
+	{
+		Mesh{
+			culling,
+			..*self
+		}
+	}
+
+	// one RGB 332 color per vertex, used by rasterize_smooth instead of the single mesh color
+	pub fn with_vertex_colors(&self, vertex_colors: &'static [u8]) -> Mesh
+	{
+		assert!(vertex_colors.len() >= self.vertices.len(), "every vertex needs a color");
+
+		Mesh{
+			vertex_colors,
+			..*self
 		}
 	}
 
-	pub fn project(&self, mvp_matrix: &mat4x4) -> [vec2f; 8]
+	// one texture coordinate per vertex, needed by rasterize_textured
+	pub fn with_uvs(&self, uvs: &'static [vec2f]) -> Mesh
 	{
-		let mut out_points = [vec2f::new(); 8];
+		assert!(uvs.len() >= self.vertices.len(), "every vertex needs a texture coordinate");
+
+		Mesh{
+			uvs,
+			..*self
+		}
+	}
 
-		for i in 0..8
+	// vertex with the mesh scale and translation applied
+	pub fn get_vertex(&self, index: usize) -> vec3f
+	{
+		self.vertices[index].scale(self.scale) + &self.translation
+	}
+
+	// clip space position of every vertex, out_points needs room for at least vertices.len() points
+	// nothing is divided by w here, that only happens after clipping
+	pub fn project(&self, mvp_matrix: &mat4x4, out_points: &mut [vec4f])
+	{
+		for (i, out_point) in out_points.iter_mut().enumerate().take(self.vertices.len())
 		{
-			let vec_projected = *mvp_matrix * &self.points[i];
+			*out_point = *mvp_matrix * &vec4f::from_vec3f(&self.get_vertex(i), 1.0f32);
+		}
+	}
 
-			out_points[i].x = f32::min(display::SCREEN_WIDTH as f32 - 1.0f32, (vec_projected.x + 1.0f32) * 0.5f32 * display::SCREEN_WIDTH as f32);
-			out_points[i].y = f32::min(display::SCREEN_HEIGHT as f32 - 1.0f32, (1.0f32 - ((vec_projected.y + 1.0f32) * 0.5f32)) * display::SCREEN_HEIGHT as f32);
+	pub fn rasterize_wireframe(&self, framebuffer: &mut Surface, mvp_matrix: &mat4x4, viewport: &Viewport, style: &LineStyle, color: u8)
+	{
+		let mut clip_points = [vec4f::new(); MAX_MESH_VERTICES];
+		self.project(mvp_matrix, &mut clip_points);
 
-			out_points[i].x -= 30.0f32;
-			out_points[i].y += 30.0f32;
+		for edge in self.edges
+		{
+			if let Some((begin, end)) = clip::clip_line(&clip_points[edge[0] as usize], &clip_points[edge[1] as usize])
+			{
+				framebuffer.draw_line_styled(&viewport.to_screen(&begin.divide()).xy(), &viewport.to_screen(&end.divide()).xy(), style, color);
+			}
 		}
+	}
+
+	// model space normal of a face, follows the counter-clockwise winding
+	pub fn face_normal(&self, face: &[u16; 3]) -> vec3f
+	{
+		let a = &self.vertices[face[0] as usize];
+		let b = &self.vertices[face[1] as usize];
+		let c = &self.vertices[face[2] as usize];
 
-		out_points
+		b.sub(a).cross(&c.sub(a)).normalize()
 	}
 
-	pub fn rasterize_wireframe(&self, framebuffer: &mut [u8], mvp_matrix: &mat4x4, color: u8)
+	pub fn rasterize(&self, framebuffer: &mut Surface, depthbuffer: &mut [u16], mvp_matrix: &mat4x4, viewport: &Viewport, color: u8)
 	{
-		let projected_points = self.project(mvp_matrix);
+		self.bin(&mut DrawTriangles{framebuffer, depthbuffer}, mvp_matrix, viewport, color);
+	}
 
-		draw_line(framebuffer, &projected_points[0], &projected_points[1], color);
-		draw_line(framebuffer, &projected_points[1], &projected_points[2], color);
-		draw_line(framebuffer, &projected_points[2], &projected_points[3], color);
-		draw_line(framebuffer, &projected_points[3], &projected_points[0], color);
+	// flat shading, every face gets one color based on the angle between its normal and the light
+	// model_matrix is the part of the mvp matrix that takes the mesh into world space
+	pub fn rasterize_lit(&self, framebuffer: &mut Surface, depthbuffer: &mut [u16], model_matrix: &mat4x4, mvp_matrix: &mat4x4, viewport: &Viewport, light: &DirectionalLight, color: u8)
+	{
+		self.bin_lit(&mut DrawTriangles{framebuffer, depthbuffer}, model_matrix, mvp_matrix, viewport, light, color);
+	}
 
-		draw_line(framebuffer, &projected_points[4], &projected_points[5], color);
-		draw_line(framebuffer, &projected_points[5], &projected_points[6], color);
-		draw_line(framebuffer, &projected_points[6], &projected_points[7], color);
-		draw_line(framebuffer, &projected_points[7], &projected_points[4], color);
+	// gouraud shading, lighting is done per vertex and the resulting colors are interpolated across the faces
+	// vertex_colors are used as the base color when the mesh has them, otherwise color is
+	pub fn rasterize_smooth(&self, framebuffer: &mut Surface, depthbuffer: &mut [u16], model_matrix: &mat4x4, mvp_matrix: &mat4x4, viewport: &Viewport, light: &DirectionalLight, color: u8, dither: bool)
+	{
+		self.bin_smooth(&mut DrawTriangles{framebuffer, depthbuffer}, model_matrix, mvp_matrix, viewport, light, color, dither);
+	}
+
+	// gouraud shaded and perspective correct texture mapped, the mesh needs uvs
+	// the texture is tinted by the lit vertex colors, so pass colors::WHITE to get the plain texture
+	pub fn rasterize_textured(&self, framebuffer: &mut Surface, depthbuffer: &mut [u16], model_matrix: &mat4x4, mvp_matrix: &mat4x4, viewport: &Viewport, light: &DirectionalLight, color: u8, texture: &Texture, dither: bool)
+	{
+		self.bin_textured(&mut DrawTriangles{framebuffer, depthbuffer}, model_matrix, mvp_matrix, viewport, light, color, texture, dither);
+	}
 
-		draw_line(framebuffer, &projected_points[0], &projected_points[4], color);
-		draw_line(framebuffer, &projected_points[5], &projected_points[1], color);
-		draw_line(framebuffer, &projected_points[6], &projected_points[2], color);
-		draw_line(framebuffer, &projected_points[7], &projected_points[3], color);
+	// the bin_* functions are the rasterize_* ones without the drawing, the clipped and culled
+	// screen space triangles go to sink instead
+	pub fn bin<S: TriangleSink>(&self, sink: &mut S, mvp_matrix: &mat4x4, viewport: &Viewport, color: u8)
+	{
+		self.bin_faces(sink, mvp_matrix, viewport, |_| color);
 	}
 
-	pub fn rasterize(&self, framebuffer: &mut [u8], mvp_matrix: &mat4x4, color: u8)
+	pub fn bin_lit<S: TriangleSink>(&self, sink: &mut S, model_matrix: &mat4x4, mvp_matrix: &mat4x4, viewport: &Viewport, light: &DirectionalLight, color: u8)
 	{
-		let projected_points = self.project(mvp_matrix);
+		self.bin_faces(sink, mvp_matrix, viewport, |face| {
+			let world_normal = model_matrix.transform_direction(&self.face_normal(face)).normalize();
+			shade_pixel(color, light.intensity(&world_normal))
+		});
+	}
 
-		// right
-		triangle::from(&projected_points[0], &projected_points[1], &projected_points[5]).draw(framebuffer, color);
-		triangle::from(&projected_points[0], &projected_points[5], &projected_points[4]).draw(framebuffer, color);
+	pub fn bin_smooth<S: TriangleSink>(&self, sink: &mut S, model_matrix: &mat4x4, mvp_matrix: &mat4x4, viewport: &Viewport, light: &DirectionalLight, color: u8, dither: bool)
+	{
+		self.bin_shaded(sink, model_matrix, mvp_matrix, viewport, light, color, None, dither);
+	}
 
-		// back
-		triangle::from(&projected_points[0], &projected_points[1], &projected_points[2]).draw(framebuffer, color);
-		triangle::from(&projected_points[0], &projected_points[3], &projected_points[2]).draw(framebuffer, color);
+	pub fn bin_textured<S: TriangleSink>(&self, sink: &mut S, model_matrix: &mat4x4, mvp_matrix: &mat4x4, viewport: &Viewport, light: &DirectionalLight, color: u8, texture: &Texture, dither: bool)
+	{
+		self.bin_shaded(sink, model_matrix, mvp_matrix, viewport, light, color, Some(texture), dither);
+	}
 
-		// left
-		triangle::from(&projected_points[2], &projected_points[3], &projected_points[7]).draw(framebuffer, color);
-		triangle::from(&projected_points[2], &projected_points[6], &projected_points[7]).draw(framebuffer, color);
+	fn bin_shaded<S: TriangleSink>(&self, sink: &mut S, model_matrix: &mat4x4, mvp_matrix: &mat4x4, viewport: &Viewport, light: &DirectionalLight, color: u8, texture: Option<&Texture>, dither: bool)
+	{
+		// the only per vertex buffer here, the colors hold the summed up normals of the faces
+		// around every vertex until the vertices are lit
+		let mut vertices = [ClipVertex::new(vec4f::new()); MAX_MESH_VERTICES];
 
-		// front
-		triangle::from(&projected_points[6], &projected_points[5], &projected_points[4]).draw(framebuffer, color);
-		triangle::from(&projected_points[6], &projected_points[4], &projected_points[7]).draw(framebuffer, color);
+		for face in self.triangles
+		{
+			let face_normal = self.face_normal(face);
 
-		// top
-		triangle::from(&projected_points[1], &projected_points[3], &projected_points[4]).draw(framebuffer, color);
-		triangle::from(&projected_points[3], &projected_points[4], &projected_points[7]).draw(framebuffer, color);
+			for index in face
+			{
+				vertices[*index as usize].color = vertices[*index as usize].color + &face_normal;
+			}
+		}
 
-		// top
-		triangle::from(&projected_points[1], &projected_points[2], &projected_points[5]).draw(framebuffer, color);
-		triangle::from(&projected_points[2], &projected_points[5], &projected_points[6]).draw(framebuffer, color);
+		for (i, vertex) in vertices.iter_mut().enumerate().take(self.vertices.len())
+		{
+			let base_color = if self.vertex_colors.is_empty() { color } else { self.vertex_colors[i] };
+			let (r, g, b) = split_pixel(base_color);
+
+			let world_normal = model_matrix.transform_direction(&vertex.color.normalize()).normalize();
+			let intensity = light.intensity(&world_normal);
+
+			*vertex = ClipVertex{
+				position: *mvp_matrix * &vec4f::from_vec3f(&self.get_vertex(i), 1.0f32),
+				color: vec3f{x: r as f32, y: g as f32, z: b as f32}.scale(intensity),
+				uv: if self.uvs.is_empty() { vec2f::new() } else { self.uvs[i] }
+			};
+		}
+
+		for face in self.triangles
+		{
+			let corners = [vertices[face[0] as usize], vertices[face[1] as usize], vertices[face[2] as usize]];
+
+			clip::for_each_clipped_triangle(&corners, |a, b, c| {
+				let projected_triangle = shaded_triangle::from(&to_screen_vertex(a, viewport), &to_screen_vertex(b, viewport), &to_screen_vertex(c, viewport));
+
+				if !projected_triangle.is_culled(self.culling)
+				{
+					sink.shaded(&projected_triangle, texture, dither);
+				}
+			});
+		}
+	}
+
+	fn bin_faces<S: TriangleSink, F: Fn(&[u16; 3]) -> u8>(&self, sink: &mut S, mvp_matrix: &mat4x4, viewport: &Viewport, face_color: F)
+	{
+		let mut clip_points = [vec4f::new(); MAX_MESH_VERTICES];
+		self.project(mvp_matrix, &mut clip_points);
+
+		for face in self.triangles
+		{
+			let corners = [
+				ClipVertex::new(clip_points[face[0] as usize]),
+				ClipVertex::new(clip_points[face[1] as usize]),
+				ClipVertex::new(clip_points[face[2] as usize])
+			];
+			let color = face_color(face);
+
+			clip::for_each_clipped_triangle(&corners, |a, b, c| {
+				let projected_triangle = triangle3d::from(
+					&viewport.to_screen(&a.position.divide()),
+					&viewport.to_screen(&b.position.divide()),
+					&viewport.to_screen(&c.position.divide())
+				);
+
+				if !projected_triangle.is_culled(self.culling)
+				{
+					sink.flat(&projected_triangle, color);
+				}
+			});
+		}
+	}
+}
+
+// perspective divide of a clipped vertex, uv gets divided by w for perspective correct interpolation
+fn to_screen_vertex(clip_vertex: &ClipVertex, viewport: &Viewport) -> Vertex
+{
+	let inv_w = 1.0f32 / clip_vertex.position.w;
+
+	Vertex{
+		position: viewport.to_screen(&clip_vertex.position.divide()),
+		color: clip_vertex.color,
+		uv: clip_vertex.uv.scale(inv_w),
+		inv_w
+	}
+}
+
+pub mod cube
+{
+	use crate::vector::{vec2f, vec3f};
+	use super::{Mesh, Culling};
+
+	static VERTICES: [vec3f; 8] = [
+		vec3f{x: 1.0f32, y: 1.0f32, z: 1.0f32},
+		vec3f{x: 1.0f32, y: -1.0f32, z: 1.0f32},
+		vec3f{x: -1.0f32, y: -1.0f32, z: 1.0f32},
+		vec3f{x: -1.0f32, y: 1.0f32, z: 1.0f32},
+		vec3f{x: 1.0f32, y: 1.0f32, z: -1.0f32},
+		vec3f{x: 1.0f32, y: -1.0f32, z: -1.0f32},
+		vec3f{x: -1.0f32, y: -1.0f32, z: -1.0f32},
+		vec3f{x: -1.0f32, y: 1.0f32, z: -1.0f32}
+	];
+
+	// counter-clockwise when looking at each face from the outside
+	static TRIANGLES: [[u16; 3]; 12] = [
+		// right (+x)
+		[0, 1, 5],
+		[0, 5, 4],
+
+		// left (-x)
+		[2, 3, 7],
+		[2, 7, 6],
+
+		// top (+y)
+		[0, 4, 7],
+		[0, 7, 3],
+
+		// bottom (-y)
+		[1, 6, 5],
+		[1, 2, 6],
+
+		// front (+z)
+		[0, 3, 2],
+		[0, 2, 1],
+
+		// back (-z)
+		[4, 5, 6],
+		[4, 6, 7]
+	];
+
+	static EDGES: [[u16; 2]; 12] = [
+		[0, 1], [1, 2], [2, 3], [3, 0],
+		[4, 5], [5, 6], [6, 7], [7, 4],
+		[0, 4], [5, 1], [6, 2], [7, 3]
+	];
+
+	// separate vertices for every face so each one can map the whole texture
+	// corners go bottom left, bottom right, top right, top left when looking at the face from the outside
+	static TEXTURED_VERTICES: [vec3f; 24] = [
+		// right (+x)
+		vec3f{x: 1.0f32, y: -1.0f32, z: 1.0f32},
+		vec3f{x: 1.0f32, y: -1.0f32, z: -1.0f32},
+		vec3f{x: 1.0f32, y: 1.0f32, z: -1.0f32},
+		vec3f{x: 1.0f32, y: 1.0f32, z: 1.0f32},
+		// left (-x)
+		vec3f{x: -1.0f32, y: -1.0f32, z: -1.0f32},
+		vec3f{x: -1.0f32, y: -1.0f32, z: 1.0f32},
+		vec3f{x: -1.0f32, y: 1.0f32, z: 1.0f32},
+		vec3f{x: -1.0f32, y: 1.0f32, z: -1.0f32},
+		// top (+y)
+		vec3f{x: -1.0f32, y: 1.0f32, z: 1.0f32},
+		vec3f{x: 1.0f32, y: 1.0f32, z: 1.0f32},
+		vec3f{x: 1.0f32, y: 1.0f32, z: -1.0f32},
+		vec3f{x: -1.0f32, y: 1.0f32, z: -1.0f32},
+		// bottom (-y)
+		vec3f{x: -1.0f32, y: -1.0f32, z: -1.0f32},
+		vec3f{x: 1.0f32, y: -1.0f32, z: -1.0f32},
+		vec3f{x: 1.0f32, y: -1.0f32, z: 1.0f32},
+		vec3f{x: -1.0f32, y: -1.0f32, z: 1.0f32},
+		// front (+z)
+		vec3f{x: -1.0f32, y: -1.0f32, z: 1.0f32},
+		vec3f{x: 1.0f32, y: -1.0f32, z: 1.0f32},
+		vec3f{x: 1.0f32, y: 1.0f32, z: 1.0f32},
+		vec3f{x: -1.0f32, y: 1.0f32, z: 1.0f32},
+		// back (-z)
+		vec3f{x: 1.0f32, y: -1.0f32, z: -1.0f32},
+		vec3f{x: -1.0f32, y: -1.0f32, z: -1.0f32},
+		vec3f{x: -1.0f32, y: 1.0f32, z: -1.0f32},
+		vec3f{x: 1.0f32, y: 1.0f32, z: -1.0f32}
+	];
+
+	static TEXTURED_UVS: [vec2f; 24] = [
+		// right (+x)
+		vec2f{x: 0.0f32, y: 1.0f32},
+		vec2f{x: 1.0f32, y: 1.0f32},
+		vec2f{x: 1.0f32, y: 0.0f32},
+		vec2f{x: 0.0f32, y: 0.0f32},
+		// left (-x)
+		vec2f{x: 0.0f32, y: 1.0f32},
+		vec2f{x: 1.0f32, y: 1.0f32},
+		vec2f{x: 1.0f32, y: 0.0f32},
+		vec2f{x: 0.0f32, y: 0.0f32},
+		// top (+y)
+		vec2f{x: 0.0f32, y: 1.0f32},
+		vec2f{x: 1.0f32, y: 1.0f32},
+		vec2f{x: 1.0f32, y: 0.0f32},
+		vec2f{x: 0.0f32, y: 0.0f32},
+		// bottom (-y)
+		vec2f{x: 0.0f32, y: 1.0f32},
+		vec2f{x: 1.0f32, y: 1.0f32},
+		vec2f{x: 1.0f32, y: 0.0f32},
+		vec2f{x: 0.0f32, y: 0.0f32},
+		// front (+z)
+		vec2f{x: 0.0f32, y: 1.0f32},
+		vec2f{x: 1.0f32, y: 1.0f32},
+		vec2f{x: 1.0f32, y: 0.0f32},
+		vec2f{x: 0.0f32, y: 0.0f32},
+		// back (-z)
+		vec2f{x: 0.0f32, y: 1.0f32},
+		vec2f{x: 1.0f32, y: 1.0f32},
+		vec2f{x: 1.0f32, y: 0.0f32},
+		vec2f{x: 0.0f32, y: 0.0f32}
+	];
+
+	static TEXTURED_TRIANGLES: [[u16; 3]; 12] = [
+		[0, 1, 2], [0, 2, 3],
+		[4, 5, 6], [4, 6, 7],
+		[8, 9, 10], [8, 10, 11],
+		[12, 13, 14], [12, 14, 15],
+		[16, 17, 18], [16, 18, 19],
+		[20, 21, 22], [20, 22, 23]
+	];
+
+	static TEXTURED_EDGES: [[u16; 2]; 24] = [
+		[0, 1], [1, 2], [2, 3], [3, 0],
+		[4, 5], [5, 6], [6, 7], [7, 4],
+		[8, 9], [9, 10], [10, 11], [11, 8],
+		[12, 13], [13, 14], [14, 15], [15, 12],
+		[16, 17], [17, 18], [18, 19], [19, 16],
+		[20, 21], [21, 22], [22, 23], [23, 20]
+	];
+
+	// RGB 332 color for every corner, each axis drives one color channel
+	#[allow(clippy::unusual_byte_groupings)]
+	static CORNER_COLORS: [u8; 8] = [
+		0b111_111_11,
+		0b111_000_11,
+		0b000_000_11,
+		0b000_111_11,
+		0b111_111_00,
+		0b111_000_00,
+		0b000_000_00,
+		0b000_111_00
+	];
+
+	// 2x2x2 cube centered at the origin, it's convex so back faces are never visible
+	pub fn new() -> Mesh
+	{
+		Mesh::new(&VERTICES, &TRIANGLES, &EDGES).with_culling(Culling::Back)
+	}
+
+	// same cube with a color gradient across it, meant for rasterize_smooth
+	pub fn new_colored() -> Mesh
+	{
+		new().with_vertex_colors(&CORNER_COLORS)
+	}
+
+	// cube with the whole texture on every face, meant for rasterize_textured
+	pub fn new_textured() -> Mesh
+	{
+		Mesh::new(&TEXTURED_VERTICES, &TEXTURED_TRIANGLES, &TEXTURED_EDGES).with_culling(Culling::Back).with_uvs(&TEXTURED_UVS)
 	}
 }
 
@@ -162,18 +537,18 @@ impl triangle
 	}
 
 	// get the triangle points, sort them, translate them to the center of the coordinate system
-	pub fn draw(&self, framebuffer: &mut [u8], color: u8)
+	pub fn draw(&self, framebuffer: &mut Surface, color: u8)
 	{
-		let mut points = self.get_sorted_verts();
+		let points = self.get_sorted_verts();
 
 		self.rasterize(framebuffer, &points[1], &points[0], &points[2], color);
 		self.rasterize(framebuffer, &points[1], &points[2], &points[0], color);
 	}
 
 	// tip is a common point between the two lines 
-	fn rasterize(&self, framebuffer: &mut [u8], origin: &vec2f, tip: &vec2f, end: &vec2f, color: u8)
+	fn rasterize(&self, framebuffer: &mut Surface, origin: &vec2f, tip: &vec2f, end: &vec2f, color: u8)
 	{
-		let mut points: [vec2f; 2] = [
+		let points: [vec2f; 2] = [
 			origin - tip,
 			end - tip
 		];
@@ -186,9 +561,8 @@ impl triangle
 		{
 			for y in 0..(points[0].y as i32 + 1)
 			{
-				framebuffer::draw_line_horizontal(
-					framebuffer,
-					(y as f32 + tip.y + 0.5f32), 
+				framebuffer.draw_line_horizontal(
+					y as f32 + tip.y + 0.5f32,
 					(y as f32 * slope1) + tip.x,
 					(y as f32 * slope2) + tip.x,
 					color);
@@ -198,9 +572,8 @@ impl triangle
 		{
 			for y in (points[0].y as i32 + 1)..0
 			{
-				framebuffer::draw_line_horizontal(
-					framebuffer,
-					(y as f32 + tip.y - 0.5f32), 
+				framebuffer.draw_line_horizontal(
+					y as f32 + tip.y - 0.5f32,
 					(y as f32 * slope1) + tip.x,
 					(y as f32 * slope2) + tip.x,
 					color);
@@ -257,13 +630,290 @@ impl triangle
 	}
 }
 
+impl Default for triangle
+{
+	fn default() -> Self {
+		triangle::new()
+	}
+}
+
+impl triangle3d
+{
+	// x and y are screen coordinates, z is the projected depth
+	pub fn from(p1: &vec3f, p2: &vec3f, p3: &vec3f) -> triangle3d
+	{
+		triangle3d {
+			points: [*p1, *p2, *p3]
+		}
+	}
+
+	// twice the area of the triangle, positive when the points are counter-clockwise on screen
+	// screen y points down so the usual cross product sign is flipped
+	pub fn signed_area(&self) -> f32
+	{
+		let a = &self.points[0];
+		let b = &self.points[1];
+		let c = &self.points[2];
+
+		(c.x - a.x) * (b.y - a.y) - (b.x - a.x) * (c.y - a.y)
+	}
+
+	// degenerate triangles have no facing and get culled by both Back and Front
+	pub fn is_culled(&self, culling: Culling) -> bool
+	{
+		let area = self.signed_area();
+
+		match culling
+		{
+			Culling::None => false,
+			Culling::Back => area <= 0.0f32,
+			Culling::Front => area >= 0.0f32
+		}
+	}
+
+	// same as triangle::draw, but every fragment goes through the depth test
+	pub fn draw(&self, framebuffer: &mut Surface, depthbuffer: &mut [u16], color: u8)
+	{
+		let points = self.get_sorted_verts();
+
+		self.rasterize(framebuffer, depthbuffer, &points[1], &points[0], &points[2], color);
+		self.rasterize(framebuffer, depthbuffer, &points[1], &points[2], &points[0], color);
+	}
+
+	// tip is a common point between the two lines, depth is interpolated along both of them
+	fn rasterize(&self, framebuffer: &mut Surface, depthbuffer: &mut [u16], origin: &vec3f, tip: &vec3f, end: &vec3f, color: u8)
+	{
+		let points: [vec3f; 2] = [
+			vec3f{x: origin.x - tip.x, y: origin.y - tip.y, z: origin.z - tip.z},
+			vec3f{x: end.x - tip.x, y: end.y - tip.y, z: end.z - tip.z}
+		];
+
+		// slope will be x/y and z/y since we iterate & draw by y
+		let slope1 = points[0].x / points[0].y;
+		let slope2 = points[1].x / points[1].y;
+		let depth_slope1 = points[0].z / points[0].y;
+		let depth_slope2 = points[1].z / points[1].y;
+
+		if points[0].y > 0.0f32
+		{
+			for y in 0..(points[0].y as i32 + 1)
+			{
+				framebuffer.draw_line_horizontal_depth(
+					depthbuffer,
+					y as f32 + tip.y + 0.5f32,
+					(y as f32 * slope1) + tip.x,
+					(y as f32 * slope2) + tip.x,
+					(y as f32 * depth_slope1) + tip.z,
+					(y as f32 * depth_slope2) + tip.z,
+					color);
+			}
+		}
+		else 
+		{
+			for y in (points[0].y as i32 + 1)..0
+			{
+				framebuffer.draw_line_horizontal_depth(
+					depthbuffer,
+					y as f32 + tip.y - 0.5f32,
+					(y as f32 * slope1) + tip.x,
+					(y as f32 * slope2) + tip.x,
+					(y as f32 * depth_slope1) + tip.z,
+					(y as f32 * depth_slope2) + tip.z,
+					color);
+			}
+		}
+	}
+
+	fn get_sorted_verts(&self) -> [vec3f; 3]
+	{
+		let mut points: [vec3f; 3] = self.points;
+
+		compare_swap_verts3d(&mut points, 0, 1);
+		compare_swap_verts3d(&mut points, 1, 2);
+		compare_swap_verts3d(&mut points, 0, 1);
+
+		points
+	}
+}
+
+impl Vertex
+{
+	pub fn add(&self, v: &Vertex) -> Vertex
+	{
+		Vertex{
+			position: self.position.add(&v.position),
+			color: self.color.add(&v.color),
+			uv: self.uv.add(&v.uv),
+			inv_w: self.inv_w + v.inv_w
+		}
+	}
+
+	pub fn sub(&self, v: &Vertex) -> Vertex
+	{
+		Vertex{
+			position: self.position.sub(&v.position),
+			color: self.color.sub(&v.color),
+			uv: self.uv.sub(&v.uv),
+			inv_w: self.inv_w - v.inv_w
+		}
+	}
+
+	pub fn scale(&self, factor: f32) -> Vertex
+	{
+		Vertex{
+			position: self.position.scale(factor),
+			color: self.color.scale(factor),
+			uv: self.uv.scale(factor),
+			inv_w: self.inv_w * factor
+		}
+	}
+}
+
+impl shaded_triangle
+{
+	pub fn from(p1: &Vertex, p2: &Vertex, p3: &Vertex) -> shaded_triangle
+	{
+		shaded_triangle {
+			points: [*p1, *p2, *p3]
+		}
+	}
+
+	pub fn is_culled(&self, culling: Culling) -> bool
+	{
+		triangle3d::from(&self.points[0].position, &self.points[1].position, &self.points[2].position).is_culled(culling)
+	}
+
+	// same split into two halves as triangle::draw, every attribute is interpolated along the edges and spans
+	// without a texture the interpolated color is drawn directly, otherwise it tints the texture
+	pub fn draw(&self, framebuffer: &mut Surface, depthbuffer: &mut [u16], texture: Option<&Texture>, dither: bool)
+	{
+		let points = self.get_sorted_verts();
+
+		self.rasterize(framebuffer, depthbuffer, &points[1], &points[0], &points[2], texture, dither);
+		self.rasterize(framebuffer, depthbuffer, &points[1], &points[2], &points[0], texture, dither);
+	}
+
+	// tip is a common point between the two lines
+	fn rasterize(&self, framebuffer: &mut Surface, depthbuffer: &mut [u16], origin: &Vertex, tip: &Vertex, end: &Vertex, texture: Option<&Texture>, dither: bool)
+	{
+		let edge1 = origin.sub(tip);
+		let edge2 = end.sub(tip);
+
+		// change of every attribute per scanline along both edges
+		let step1 = edge1.scale(1.0f32 / edge1.position.y);
+		let step2 = edge2.scale(1.0f32 / edge2.position.y);
+
+		if edge1.position.y > 0.0f32
+		{
+			for y in 0..(edge1.position.y as i32 + 1)
+			{
+				draw_span(
+					framebuffer,
+					depthbuffer,
+					y as f32 + tip.position.y + 0.5f32,
+					&tip.add(&step1.scale(y as f32)),
+					&tip.add(&step2.scale(y as f32)),
+					texture,
+					dither);
+			}
+		}
+		else 
+		{
+			for y in (edge1.position.y as i32 + 1)..0
+			{
+				draw_span(
+					framebuffer,
+					depthbuffer,
+					y as f32 + tip.position.y - 0.5f32,
+					&tip.add(&step1.scale(y as f32)),
+					&tip.add(&step2.scale(y as f32)),
+					texture,
+					dither);
+			}
+		}
+	}
+
+	fn get_sorted_verts(&self) -> [Vertex; 3]
+	{
+		let mut points: [Vertex; 3] = self.points;
+
+		for (v1, v2) in [(0, 1), (1, 2), (0, 1)]
+		{
+			// sort by y, v1 should have higher y value
+			if points[v1].position.y < points[v2].position.y
+			{
+				points.swap(v1, v2);
+			}
+		}
+
+		points
+	}
+}
+
+// horizontal span between two interpolated vertices, left and right are sorted out here
+fn draw_span(framebuffer: &mut Surface, depthbuffer: &mut [u16], y: f32, begin: &Vertex, end: &Vertex, texture: Option<&Texture>, dither: bool)
+{
+	let (left, right) = if begin.position.x < end.position.x { (begin, end) } else { (end, begin) };
+
+	let width = right.position.x - left.position.x;
+
+	if width <= 0.0f32
+	{
+		return;
+	}
+
+	let step = right.sub(left).scale(1.0f32 / width);
+
+	let (row, left_x, right_x) = match framebuffer.clip_span(y, left.position.x, right.position.x)
+	{
+		Some(span) => span,
+		None => return
+	};
+	let pixel_y = y as usize;
+	framebuffer.mark_damaged(left_x, pixel_y, right_x - left_x, 1);
+
+	for x in left_x..right_x
+	{
+		let fragment = left.add(&step.scale(x as f32 - left.position.x));
+
+		let (r, g, b) = match texture
+		{
+			None => (fragment.color.x as u8, fragment.color.y as u8, fragment.color.z as u8),
+			Some(texture) => {
+				// undo the divide by w from Vertex::uv
+				let uv = fragment.uv.scale(1.0f32 / fragment.inv_w);
+				let (tex_r, tex_g, tex_b) = split_pixel(texture.sample_nearest(uv.x, uv.y));
+
+				(
+					(tex_r as f32 * fragment.color.x / 255.0f32) as u8,
+					(tex_g as f32 * fragment.color.y / 255.0f32) as u8,
+					(tex_b as f32 * fragment.color.z / 255.0f32) as u8
+				)
+			}
+		};
+		let color = if dither { make_pixel_dithered(r, g, b, x, pixel_y) } else { make_pixel(r, g, b) };
+
+		if depthbuffer::test_and_set(depthbuffer, row + x, depthbuffer::to_depth(fragment.position.z))
+		{
+			framebuffer.data[row + x] = color;
+		}
+	}
+}
+
+// sort by y, v1 should have higher y value
+fn compare_swap_verts3d(points: &mut [vec3f; 3], v1: usize, v2: usize)
+{
+	if points[v1].y < points[v2].y
+	{
+		points.swap(v1, v2);
+	}
+}
+
 // sort by y, v1 should have higher y value
 fn compare_swap_verts(points: &mut [vec2f; 3], v1: usize, v2: usize)
 {
 	if points[v1].y < points[v2].y
 	{
-		let temp = points[v1].clone();
-		points[v1] = points[v2].clone();
-		points[v2] = temp;
+		points.swap(v1, v2);
 	}
 }
\ No newline at end of file
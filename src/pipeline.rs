@@ -0,0 +1,271 @@
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicU8, Ordering};
+use crate::scene::Input;
+
+// Frame scheduling for rendering on one core while the other one sends finished frames to the
+// display and reads the buttons. The two sides share N frames, each of them belongs to exactly
+// one side at a time and changes hands with a message through a Fifo:
+//
+//   renderer:  next_frame -> render into it -> submit     (FrameReady goes to the presenter)
+//   presenter: next_frame -> send it to the display -> frame_sent     (FrameFree goes back)
+//
+// Frames are presented in the order they were submitted. On the RP2040 the Fifo is the SIO
+// FIFO between the cores, which holds 8 words, so up to 8 frames never block a write.
+// The schedules only deal with frame indices, the frames themselves live in FrameCells.
+
+// one direction of a word sized channel between the two sides
+pub trait Fifo
+{
+	// waits for room if the fifo is full
+	fn write(&mut self, value: u32);
+	// None when there's nothing to read
+	fn read(&mut self) -> Option<u32>;
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Message
+{
+	// the renderer finished a frame, it belongs to the presenter now
+	FrameReady(usize),
+	// the presenter is done with a frame, it belongs to the renderer again
+	FrameFree(usize)
+}
+
+const FRAME_READY: u32 = 0x5200_0000u32;
+const FRAME_FREE: u32 = 0x4600_0000u32;
+const KIND_MASK: u32 = 0xFF00_0000u32;
+
+impl Message
+{
+	pub fn encode(&self) -> u32
+	{
+		match self
+		{
+			Message::FrameReady(index) => FRAME_READY | *index as u32,
+			Message::FrameFree(index) => FRAME_FREE | *index as u32
+		}
+	}
+
+	// anything else is None, the RP2040's FIFO can still hold words from starting core1
+	pub fn decode(value: u32) -> Option<Message>
+	{
+		let index = (value & !KIND_MASK) as usize;
+
+		match value & KIND_MASK
+		{
+			FRAME_READY => Some(Message::FrameReady(index)),
+			FRAME_FREE => Some(Message::FrameFree(index)),
+			_ => None
+		}
+	}
+}
+
+// the rendering side, it starts out with all frames
+pub struct Renderer<const N: usize>
+{
+	owned: [bool; N]
+}
+
+impl<const N: usize> Renderer<N>
+{
+	pub const fn new() -> Renderer<N>
+	{
+		Renderer{
+			owned: [true; N]
+		}
+	}
+
+	pub fn owns(&self, index: usize) -> bool
+	{
+		self.owned[index]
+	}
+
+	// the frame to render into next, None while the presenter has all of them
+	pub fn next_frame<F: Fifo>(&mut self, fifo: &mut F) -> Option<usize>
+	{
+		while let Some(value) = fifo.read()
+		{
+			if let Some(Message::FrameFree(index)) = Message::decode(value)
+			{
+				if index < N
+				{
+					self.owned[index] = true;
+				}
+			}
+		}
+
+		self.owned.iter().position(|owned| *owned)
+	}
+
+	// hands a finished frame to the presenter, it mustn't be touched until it comes back
+	pub fn submit<F: Fifo>(&mut self, fifo: &mut F, index: usize)
+	{
+		if self.owned[index]
+		{
+			self.owned[index] = false;
+			fifo.write(Message::FrameReady(index).encode());
+		}
+	}
+}
+
+impl<const N: usize> Default for Renderer<N>
+{
+	fn default() -> Self {
+		Renderer::new()
+	}
+}
+
+// the display side, it sends one frame at a time and gives it back once it's on the display
+pub struct Presenter<const N: usize>
+{
+	sending: Option<usize>
+}
+
+impl<const N: usize> Presenter<N>
+{
+	pub const fn new() -> Presenter<N>
+	{
+		Presenter{
+			sending: None
+		}
+	}
+
+	// the frame being sent
+	pub fn sending(&self) -> Option<usize>
+	{
+		self.sending
+	}
+
+	// The next frame to send, if one has been submitted. Nothing is read while a frame is still
+	// being sent, the frames after it wait in the fifo and keep their order.
+	pub fn next_frame<F: Fifo>(&mut self, fifo: &mut F) -> Option<usize>
+	{
+		if self.sending.is_some()
+		{
+			return None;
+		}
+
+		while let Some(value) = fifo.read()
+		{
+			if let Some(Message::FrameReady(index)) = Message::decode(value)
+			{
+				if index < N
+				{
+					self.sending = Some(index);
+					return self.sending;
+				}
+			}
+		}
+
+		None
+	}
+
+	// the frame from next_frame has been sent, the renderer can have it back
+	pub fn frame_sent<F: Fifo>(&mut self, fifo: &mut F)
+	{
+		if let Some(index) = self.sending.take()
+		{
+			fifo.write(Message::FrameFree(index).encode());
+		}
+	}
+}
+
+impl<const N: usize> Default for Presenter<N>
+{
+	fn default() -> Self {
+		Presenter::new()
+	}
+}
+
+// Storage for the frames shared between the cores. Nothing stops both cores from getting the
+// same frame, get is only sound for a frame the calling side owns according to its schedule.
+pub struct FrameCells<T, const N: usize>
+{
+	frames: UnsafeCell<[T; N]>
+}
+
+// the schedules make sure every frame is only used by one core at a time
+unsafe impl<T: Send, const N: usize> Sync for FrameCells<T, N> {}
+
+impl<T, const N: usize> FrameCells<T, N>
+{
+	pub const fn new(frames: [T; N]) -> FrameCells<T, N>
+	{
+		FrameCells{
+			frames: UnsafeCell::new(frames)
+		}
+	}
+
+	/// The frame at index, shared between the cores without any locking.
+	///
+	/// # Safety
+	///
+	/// Every index must be owned by exactly one core at a time, and only the owning core may
+	/// call get for it (Renderer::owns, Presenter::sending). Ownership only changes hands
+	/// through the FIFO Message handoff, Renderer::submit sends FrameReady and
+	/// Presenter::frame_sent sends FrameFree, and the returned reference must not be used
+	/// after the call that gives the frame away.
+	#[allow(clippy::mut_from_ref)]
+	pub unsafe fn get(&self, index: usize) -> &mut T
+	{
+		assert!(index < N);
+		&mut *(self.frames.get() as *mut T).add(index)
+	}
+}
+
+// The buttons, read on one core and used on the other. A single byte is loaded and stored as a
+// whole, so a reader never sees half of an update. The Cortex-M0+ has no atomic read-modify-write,
+// only plain loads and stores are used.
+pub struct SharedInput
+{
+	buttons: AtomicU8
+}
+
+const BUTTON_A: u8 = 1u8;
+const BUTTON_B: u8 = 2u8;
+const BUTTON_X: u8 = 4u8;
+const BUTTON_Y: u8 = 8u8;
+
+impl SharedInput
+{
+	pub const fn new() -> SharedInput
+	{
+		SharedInput{
+			buttons: AtomicU8::new(0u8)
+		}
+	}
+
+	pub fn store(&self, input: &Input)
+	{
+		let mut buttons = 0u8;
+
+		for (pressed, bit) in [(input.a, BUTTON_A), (input.b, BUTTON_B), (input.x, BUTTON_X), (input.y, BUTTON_Y)]
+		{
+			if pressed
+			{
+				buttons |= bit;
+			}
+		}
+
+		self.buttons.store(buttons, Ordering::Release);
+	}
+
+	pub fn load(&self) -> Input
+	{
+		let buttons = self.buttons.load(Ordering::Acquire);
+
+		Input{
+			a: buttons & BUTTON_A != 0,
+			b: buttons & BUTTON_B != 0,
+			x: buttons & BUTTON_X != 0,
+			y: buttons & BUTTON_Y != 0
+		}
+	}
+}
+
+impl Default for SharedInput
+{
+	fn default() -> Self {
+		SharedInput::new()
+	}
+}
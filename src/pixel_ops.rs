@@ -20,6 +20,36 @@ pub fn make_pixel(r: u8, g: u8, b: u8) -> u8
 	out_pix
 }
 
+// 4x4 bayer matrix for ordered dithering
+const BAYER_4X4: [[u8; 4]; 4] = [
+	[0, 8, 2, 10],
+	[12, 4, 14, 6],
+	[3, 11, 1, 9],
+	[15, 7, 13, 5]
+];
+
+// convert RGB 888 to RGB 332 with ordered dithering
+// every channel gets a position dependent offset of up to one quantisation step before it's truncated
+pub fn make_pixel_dithered(r: u8, g: u8, b: u8, x: usize, y: usize) -> u8
+{
+	let threshold: u16 = BAYER_4X4[y & 3][x & 3] as u16;
+
+	let red_green_offset: u8 = (threshold * (255 / 7) / 16) as u8;
+	let blue_offset: u8 = (threshold * (255 / 3) / 16) as u8;
+
+	make_pixel(r.saturating_add(red_green_offset), g.saturating_add(red_green_offset), b.saturating_add(blue_offset))
+}
+
+// convert RGB 332 to RGB 888, the inverse of make_pixel
+pub fn split_pixel(pix: u8) -> (u8, u8, u8)
+{
+	let r: u8 = ((pix & 0b1110_0000) >> 5) * (255 / 7);
+	let g: u8 = ((pix & 0b0001_1100) >> 2) * (255 / 7);
+	let b: u8 = (pix & 0b0000_0011) * (255 / 3);
+
+	(r, g, b)
+}
+
 // convert RGB 888 to RGB 565
 pub fn make_pixel3(r: u8, g: u8, b: u8) -> u16
 {
@@ -64,6 +94,32 @@ pub fn convert_pixel(pix: u8) -> u16
 	out_pix
 }
 
+// convert RGB 565 to RGB 888, the low bits are filled from the high ones so full intensity stays 255
+pub fn expand_rgb565(pix: u16) -> (u8, u8, u8)
+{
+	let r5 = ((pix >> 11) & 0b1_1111) as u8;
+	let g6 = ((pix >> 5) & 0b11_1111) as u8;
+	let b5 = (pix & 0b1_1111) as u8;
+
+	((r5 << 3) | (r5 >> 2), (g6 << 2) | (g6 >> 4), (b5 << 3) | (b5 >> 2))
+}
+
+// mix two channel values, alpha 255 is all src and 0 is all dst
+pub fn blend_channel(dst: u8, src: u8, alpha: u8) -> u8
+{
+	((dst as u16 * (255 - alpha as u16) + src as u16 * alpha as u16 + 127) / 255) as u8
+}
+
+// blend src over dst, both RGB 332, every channel is mixed at its own precision
+pub fn blend_pixel(dst: u8, src: u8, alpha: u8) -> u8
+{
+	let r = blend_channel(red_pixel_kernel(dst) >> 5, red_pixel_kernel(src) >> 5, alpha);
+	let g = blend_channel(green_pixel_kernel(dst) >> 2, green_pixel_kernel(src) >> 2, alpha);
+	let b = blend_channel(blue_pixel_kernel(dst), blue_pixel_kernel(src), alpha);
+
+	(r << 5) | (g << 2) | b
+}
+
 // in : RGB 332
 // out : RGB 332
 pub fn red_pixel_kernel(pix: u8) -> u8
@@ -0,0 +1,238 @@
+use crate::vector::{vec2f, vec3f};
+use crate::pixel_ops::colors;
+use crate::text::StrokeFont;
+use crate::layout::{Align, TextLayout, draw_text_layout};
+use crate::matrix::mat4x4;
+use crate::light::DirectionalLight;
+use crate::texture::Texture;
+use crate::viewport::Viewport;
+use crate::framebuffer::Surface;
+use crate::line::LineStyle;
+use crate::model::{self, Mesh};
+use crate::tiles::TriangleBins;
+
+// The spinning cube demo. The firmware feeds it button presses and sends the
+// framebuffer to the display, the simulator feeds it scripted input and writes files.
+
+// ferris.raw is a 64x64 RGB 332 image, stored row by row
+static FERRIS: &[u8] = include_bytes!("ferris.raw");
+const FERRIS_WIDTH: usize = 64;
+const FERRIS_HEIGHT: usize = 64;
+
+// fails the build if ferris.raw doesn't match its size
+const _: () = assert!(include_bytes!("ferris.raw").len() == FERRIS_WIDTH * FERRIS_HEIGHT);
+
+// buttons held down during the current frame
+#[derive(Clone, Copy, Default)]
+pub struct Input
+{
+	pub a: bool,
+	pub b: bool,
+	pub x: bool,
+	pub y: bool
+}
+
+// the B button cycles through these
+#[derive(Clone, Copy, PartialEq)]
+pub enum RenderMode
+{
+	Wireframe,
+	Flat,
+	Smooth,
+	Textured
+}
+
+impl RenderMode
+{
+	pub fn next(&self) -> RenderMode
+	{
+		match self
+		{
+			RenderMode::Wireframe => RenderMode::Flat,
+			RenderMode::Flat => RenderMode::Smooth,
+			RenderMode::Smooth => RenderMode::Textured,
+			RenderMode::Textured => RenderMode::Wireframe
+		}
+	}
+}
+
+pub struct Scene
+{
+	cube: Mesh,
+	textured_cube: Mesh,
+	texture: Texture,
+	light: DirectionalLight,
+	viewport: Viewport,
+	projection_mat: mat4x4,
+	world_to_camera_mat: mat4x4,
+	pub degrees_x: f32,
+	pub degrees_y: f32,
+	pub degrees_z: f32,
+	pub render_mode: RenderMode,
+	toggled: bool
+}
+
+impl Scene
+{
+	pub fn new(viewport: Viewport) -> Scene
+	{
+		let texture = Texture::new(FERRIS, FERRIS_WIDTH, FERRIS_HEIGHT);
+
+		// 3D projection matrix vars
+		let fov = 90.0f32; // Field of view
+		let near_plane_distance = 0.1f32; // min render distance
+		let far_plane_distance = 100.0f32; // max render distance
+
+		// 3D model translation matrix
+		let mut world_to_camera_mat: mat4x4 = mat4x4::new();
+		world_to_camera_mat.data[3][0] = 0.0f32; // X translation
+		world_to_camera_mat.data[3][1] = 0.0f32; // Y translation
+		world_to_camera_mat.data[3][2] = -40.0f32; // Z translation
+
+		Scene{
+			cube: model::cube::new_colored().scale(10.0f32),
+			textured_cube: model::cube::new_textured().scale(10.0f32),
+			texture,
+			// light coming from the top left, slightly in front of the cube
+			light: DirectionalLight::new(vec3f{x: 1.0f32, y: -1.0f32, z: -1.0f32}, 0.2f32),
+			viewport,
+			projection_mat: mat4x4::new_projection(fov, viewport.aspect_ratio(), near_plane_distance, far_plane_distance),
+			world_to_camera_mat,
+			degrees_x: 0.0f32,
+			degrees_y: 0.0f32,
+			degrees_z: 0.0f32,
+			render_mode: RenderMode::Flat,
+			toggled: false
+		}
+	}
+
+	// A, X and Y spin the cube around one axis each, releasing B switches to the next render mode
+	pub fn update(&mut self, input: &Input)
+	{
+		if input.a
+		{
+			self.degrees_x += 0.05f32;
+		}
+		if input.x
+		{
+			self.degrees_y += 0.05f32;
+		}
+		if input.y
+		{
+			self.degrees_z += 0.05f32;
+		}
+
+		if input.b
+		{
+			self.toggled = true;
+		}
+		else
+		{
+			if self.toggled
+			{
+				self.render_mode = self.render_mode.next();
+			}
+
+			self.toggled = false;
+		}
+	}
+
+	// the framebuffer and depth buffer are expected to be cleared already
+	pub fn render(&self, framebuffer: &mut Surface, depthbuffer: &mut [u16])
+	{
+		self.render_viewport(framebuffer, depthbuffer, &self.viewport);
+	}
+
+	// Renders the rows of the screen from first_row on into a framebuffer (and depth buffer)
+	// that only covers those rows, everything below the end of the framebuffer gets clipped.
+	// Rendering the screen in bands like this needs a smaller depth buffer.
+	pub fn render_band(&self, framebuffer: &mut Surface, depthbuffer: &mut [u16], first_row: usize)
+	{
+		let viewport = Viewport{
+			y: self.viewport.y - first_row as f32,
+			..self.viewport
+		};
+
+		self.render_viewport(framebuffer, depthbuffer, &viewport);
+	}
+
+	// Tiled rendering, the triangles of the frame go into bins and every strip of the screen is
+	// drawn from them with render_strip. The wireframe and the title aren't triangles, they're
+	// drawn into every strip.
+	pub fn bin<const CAPACITY: usize>(&self, bins: &mut TriangleBins<CAPACITY>)
+	{
+		let (model_mat, mvp_matrix) = self.matrices();
+		let viewport = &self.viewport;
+		let light = &self.light;
+
+		bins.clear();
+
+		match self.render_mode
+		{
+			RenderMode::Wireframe => (),
+			RenderMode::Flat => self.cube.bin_lit(bins, &model_mat, &mvp_matrix, viewport, light, colors::WHITE as u8),
+			RenderMode::Smooth => self.cube.bin_smooth(bins, &model_mat, &mvp_matrix, viewport, light, colors::WHITE as u8, true),
+			RenderMode::Textured => self.textured_cube.bin_textured(bins, &model_mat, &mvp_matrix, viewport, light, colors::WHITE as u8, &self.texture, true)
+		}
+	}
+
+	// framebuffer and depthbuffer cover only the rows of the strip and are expected to be cleared already
+	pub fn render_strip<const CAPACITY: usize>(&self, bins: &TriangleBins<CAPACITY>, framebuffer: &mut Surface, depthbuffer: &mut [u16], strip: usize)
+	{
+		let (first_row, _) = bins.strip_rows(strip);
+		let viewport = Viewport{
+			y: self.viewport.y - first_row as f32,
+			..self.viewport
+		};
+
+		bins.draw_strip(strip, framebuffer, depthbuffer);
+
+		let (_, mvp_matrix) = self.matrices();
+		self.render_overlay(framebuffer, &mvp_matrix, &viewport);
+	}
+
+	// model and model view projection matrix
+	fn matrices(&self) -> (mat4x4, mat4x4)
+	{
+		let rot_matrix_x = mat4x4::new_rot_x(self.degrees_x);
+		let rot_matrix_y = mat4x4::new_rot_y(self.degrees_y);
+		let rot_matrix_z = mat4x4::new_rot_z(self.degrees_z);
+
+		let model_mat = (rot_matrix_z * &rot_matrix_y) * &rot_matrix_x;
+		let mvp_matrix = self.projection_mat * &(self.world_to_camera_mat * &model_mat);
+
+		(model_mat, mvp_matrix)
+	}
+
+	fn render_viewport(&self, framebuffer: &mut Surface, depthbuffer: &mut [u16], viewport: &Viewport)
+	{
+		let (model_mat, mvp_matrix) = self.matrices();
+		let light = &self.light;
+
+		match self.render_mode
+		{
+			RenderMode::Wireframe => (),
+			RenderMode::Flat => self.cube.rasterize_lit(framebuffer, depthbuffer, &model_mat, &mvp_matrix, viewport, light, colors::WHITE as u8),
+			RenderMode::Smooth => self.cube.rasterize_smooth(framebuffer, depthbuffer, &model_mat, &mvp_matrix, viewport, light, colors::WHITE as u8, true),
+			RenderMode::Textured => self.textured_cube.rasterize_textured(framebuffer, depthbuffer, &model_mat, &mvp_matrix, viewport, light, colors::WHITE as u8, &self.texture, true)
+		}
+
+		self.render_overlay(framebuffer, &mvp_matrix, viewport);
+	}
+
+	// everything that doesn't go through the depth buffer, drawn after the triangles
+	fn render_overlay(&self, framebuffer: &mut Surface, mvp_matrix: &mat4x4, viewport: &Viewport)
+	{
+		if self.render_mode == RenderMode::Wireframe
+		{
+			self.cube.rasterize_wireframe(framebuffer, mvp_matrix, viewport, &LineStyle::solid(), colors::WHITE as u8);
+		}
+
+		// text doesn't influence the z buffer and should be rendered last
+		// sized from the viewport so the title fits whatever it's rendered into
+		let font = StrokeFont::new(viewport.width / 8.0f32);
+		let layout = TextLayout::new().with_align(Align::Center).with_wrap_width(Some(viewport.width));
+		let position = vec2f{x: viewport.x, y: viewport.y + viewport.height / 32.0f32};
+		draw_text_layout(framebuffer, &font, &layout, b"3D\nCUBE", position, colors::WHITE as u8);
+	}
+}
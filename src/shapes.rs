@@ -0,0 +1,393 @@
+use crate::vector::vec2f;
+use crate::framebuffer::Surface;
+
+// 2D shapes for HUDs and overlays. Everything is turned into horizontal spans and drawn
+// with Surface::draw_line_horizontal, so clipping to the surface comes for free.
+// Rectangles, circles and ellipses use integer pixel coordinates and are inclusive,
+// a circle with radius r centered on cx covers cx - r ..= cx + r.
+
+// Surface::fill_polygon takes any number of points, only the edges crossing a single row are
+// kept on the stack. A row crossing more edges than this is filled up to the leftmost ones.
+pub const MAX_ROW_CROSSINGS: usize = 64;
+
+// which parts of a self intersecting polygon count as inside
+#[derive(Clone, Copy, PartialEq)]
+pub enum FillRule
+{
+	// inside when a ray to the left crosses an odd number of edges
+	EvenOdd,
+	// inside when the edges crossed by that ray don't cancel out by direction
+	NonZero
+}
+
+// A box with its corners rounded by quarters of an rx by ry ellipse, centered on the
+// corner points (left, top), (right, top), ... Circles, ellipses and rectangles are all
+// special cases of it: a circle has all four corner centers in the same place, a rectangle
+// has zero radii.
+#[derive(Clone, Copy)]
+struct RoundedBox
+{
+	left: i32,
+	top: i32,
+	right: i32,
+	bottom: i32,
+	rx: i32,
+	ry: i32
+}
+
+impl RoundedBox
+{
+	fn rect(x: i32, y: i32, width: i32, height: i32, radius: i32) -> RoundedBox
+	{
+		// the corners can't be rounder than half the box
+		let radius = i32::max(0, i32::min(radius, i32::min(width - 1, height - 1) / 2));
+
+		RoundedBox{
+			left: x + radius,
+			top: y + radius,
+			right: x + width - 1 - radius,
+			bottom: y + height - 1 - radius,
+			rx: radius,
+			ry: radius
+		}
+	}
+
+	fn ellipse(cx: i32, cy: i32, rx: i32, ry: i32) -> RoundedBox
+	{
+		RoundedBox{
+			left: cx,
+			top: cy,
+			right: cx,
+			bottom: cy,
+			rx: i32::max(0, rx),
+			ry: i32::max(0, ry)
+		}
+	}
+
+	fn first_row(&self) -> i32
+	{
+		self.top - self.ry
+	}
+
+	fn last_row(&self) -> i32
+	{
+		self.bottom + self.ry
+	}
+
+	// the shape one pixel further in, what's left between the two is a one pixel outline
+	fn inset(&self) -> RoundedBox
+	{
+		let (left, right, rx) = if self.rx > 0 { (self.left, self.right, self.rx - 1) } else { (self.left + 1, self.right - 1, 0) };
+		let (top, bottom, ry) = if self.ry > 0 { (self.top, self.bottom, self.ry - 1) } else { (self.top + 1, self.bottom - 1, 0) };
+
+		RoundedBox{
+			left,
+			top,
+			right,
+			bottom,
+			rx,
+			ry
+		}
+	}
+
+	// inclusive range of pixels covered on row y
+	fn row(&self, y: i32) -> Option<(i32, i32)>
+	{
+		if y < self.first_row() || y > self.last_row() || self.left > self.right || self.top > self.bottom
+		{
+			return None;
+		}
+
+		let dy = i32::max(0, i32::max(self.top - y, y - self.bottom));
+
+		// the +0.5 puts the edge between pixels, so the extreme rows and columns aren't a single pixel
+		let ry = self.ry as f32 + 0.5f32;
+		let t = dy as f32 / ry;
+		let half_width = libm::floorf((self.rx as f32 + 0.5f32) * libm::sqrtf(1.0f32 - t * t)) as i32;
+
+		Some((self.left - half_width, self.right + half_width))
+	}
+}
+
+// angles are in radians, 0 points along +x and they grow towards +y, so clockwise on screen
+#[derive(Clone, Copy)]
+struct AngleRange
+{
+	start: f32,
+	sweep: f32
+}
+
+const TAU: f32 = core::f32::consts::PI * 2.0f32;
+
+impl AngleRange
+{
+	fn new(start_angle: f32, end_angle: f32) -> AngleRange
+	{
+		let sweep = end_angle - start_angle;
+
+		AngleRange{
+			start: wrap_angle(start_angle),
+			sweep: if sweep >= TAU { TAU } else { wrap_angle(sweep) }
+		}
+	}
+
+	fn contains(&self, dx: i32, dy: i32) -> bool
+	{
+		if self.sweep >= TAU || (dx == 0 && dy == 0)
+		{
+			return true;
+		}
+
+		wrap_angle(libm::atan2f(dy as f32, dx as f32) - self.start) <= self.sweep
+	}
+}
+
+fn wrap_angle(angle: f32) -> f32
+{
+	angle - libm::floorf(angle / TAU) * TAU
+}
+
+impl<'a> Surface<'a>
+{
+	pub fn fill_rect(&mut self, x: i32, y: i32, width: i32, height: i32, color: u8)
+	{
+		self.fill_rounded_box(&RoundedBox::rect(x, y, width, height, 0), color);
+	}
+
+	pub fn draw_rect(&mut self, x: i32, y: i32, width: i32, height: i32, color: u8)
+	{
+		self.draw_rounded_box(&RoundedBox::rect(x, y, width, height, 0), color);
+	}
+
+	// radius gets limited to half of the shorter side
+	pub fn fill_rounded_rect(&mut self, x: i32, y: i32, width: i32, height: i32, radius: i32, color: u8)
+	{
+		self.fill_rounded_box(&RoundedBox::rect(x, y, width, height, radius), color);
+	}
+
+	pub fn draw_rounded_rect(&mut self, x: i32, y: i32, width: i32, height: i32, radius: i32, color: u8)
+	{
+		self.draw_rounded_box(&RoundedBox::rect(x, y, width, height, radius), color);
+	}
+
+	pub fn fill_circle(&mut self, cx: i32, cy: i32, radius: i32, color: u8)
+	{
+		self.fill_rounded_box(&RoundedBox::ellipse(cx, cy, radius, radius), color);
+	}
+
+	pub fn draw_circle(&mut self, cx: i32, cy: i32, radius: i32, color: u8)
+	{
+		self.draw_rounded_box(&RoundedBox::ellipse(cx, cy, radius, radius), color);
+	}
+
+	pub fn fill_ellipse(&mut self, cx: i32, cy: i32, rx: i32, ry: i32, color: u8)
+	{
+		self.fill_rounded_box(&RoundedBox::ellipse(cx, cy, rx, ry), color);
+	}
+
+	pub fn draw_ellipse(&mut self, cx: i32, cy: i32, rx: i32, ry: i32, color: u8)
+	{
+		self.draw_rounded_box(&RoundedBox::ellipse(cx, cy, rx, ry), color);
+	}
+
+	// the part of the circle outline between the two angles, see AngleRange for the direction
+	pub fn draw_arc(&mut self, cx: i32, cy: i32, radius: i32, start_angle: f32, end_angle: f32, color: u8)
+	{
+		let shape = RoundedBox::ellipse(cx, cy, radius, radius);
+		let angles = AngleRange::new(start_angle, end_angle);
+
+		self.for_each_outline_span(&shape, |surface, y, left, right| {
+			surface.fill_span_masked(y, left, right, color, |x| angles.contains(x - cx, y - cy));
+		});
+	}
+
+	// pie slice between the two angles
+	pub fn fill_arc(&mut self, cx: i32, cy: i32, radius: i32, start_angle: f32, end_angle: f32, color: u8)
+	{
+		let shape = RoundedBox::ellipse(cx, cy, radius, radius);
+		let angles = AngleRange::new(start_angle, end_angle);
+
+		for y in self.visible_rows(&shape)
+		{
+			if let Some((left, right)) = shape.row(y)
+			{
+				self.fill_span_masked(y, left, right, color, |x| angles.contains(x - cx, y - cy));
+			}
+		}
+	}
+
+	// closed outline through all the points
+	pub fn draw_polygon(&mut self, points: &[vec2f], color: u8)
+	{
+		for (i, point) in points.iter().enumerate()
+		{
+			self.draw_line(point, &points[(i + 1) % points.len()], color);
+		}
+	}
+
+	// scanline fill, a pixel is inside when its center is, the polygon closes itself
+	pub fn fill_polygon(&mut self, points: &[vec2f], rule: FillRule, color: u8)
+	{
+		if points.len() < 3
+		{
+			return;
+		}
+
+		let mut min_y = points[0].y;
+		let mut max_y = points[0].y;
+
+		for point in points
+		{
+			min_y = f32::min(min_y, point.y);
+			max_y = f32::max(max_y, point.y);
+		}
+
+		let first_row = i32::max(0, libm::ceilf(min_y - 0.5f32) as i32);
+		let last_row = i32::min(self.height as i32 - 1, libm::ceilf(max_y - 0.5f32) as i32 - 1);
+
+		// x and direction of the edges crossing the current row, sorted by x
+		let mut crossings = [(0.0f32, 0i32); MAX_ROW_CROSSINGS];
+
+		for y in first_row..=last_row
+		{
+			let sample_y = y as f32 + 0.5f32;
+			let mut crossing_count = 0usize;
+
+			for (i, a) in points.iter().enumerate()
+			{
+				let b = &points[(i + 1) % points.len()];
+
+				// half open so a vertex shared by two edges is only counted once
+				let (low, high, direction) = if a.y < b.y { (a, b, 1i32) } else { (b, a, -1i32) };
+
+				if sample_y < low.y || sample_y >= high.y
+				{
+					continue;
+				}
+
+				let x = low.x + (sample_y - low.y) * (high.x - low.x) / (high.y - low.y);
+
+				// once the row is full only crossings left of the rightmost kept one get in
+				if crossing_count == MAX_ROW_CROSSINGS && crossings[MAX_ROW_CROSSINGS - 1].0 <= x
+				{
+					continue;
+				}
+
+				// insertion sort, rows rarely cross more than a handful of edges
+				// with a full row the rightmost crossing is pushed out
+				let mut index = usize::min(crossing_count, MAX_ROW_CROSSINGS - 1);
+				while index > 0 && crossings[index - 1].0 > x
+				{
+					crossings[index] = crossings[index - 1];
+					index -= 1;
+				}
+				crossings[index] = (x, direction);
+				crossing_count = usize::min(crossing_count + 1, MAX_ROW_CROSSINGS);
+			}
+
+			let mut winding = 0i32;
+
+			for i in 0..crossing_count
+			{
+				winding += crossings[i].1;
+
+				let inside = match rule
+				{
+					FillRule::EvenOdd => winding % 2 != 0,
+					FillRule::NonZero => winding != 0
+				};
+
+				if inside && i + 1 < crossing_count
+				{
+					// pixels whose centers are in [begin, end)
+					let begin = libm::ceilf(crossings[i].0 - 0.5f32);
+					let end = libm::ceilf(crossings[i + 1].0 - 0.5f32);
+					self.draw_line_horizontal(sample_y, begin, end, color);
+				}
+			}
+		}
+	}
+
+	fn fill_rounded_box(&mut self, shape: &RoundedBox, color: u8)
+	{
+		for y in self.visible_rows(shape)
+		{
+			if let Some((left, right)) = shape.row(y)
+			{
+				self.fill_span(y, left, right, color);
+			}
+		}
+	}
+
+	fn draw_rounded_box(&mut self, shape: &RoundedBox, color: u8)
+	{
+		self.for_each_outline_span(shape, |surface, y, left, right| surface.fill_span(y, left, right, color));
+	}
+
+	// the spans between a shape and its inset, each row has one or two of them
+	fn for_each_outline_span<F: FnMut(&mut Self, i32, i32, i32)>(&mut self, shape: &RoundedBox, mut span: F)
+	{
+		let inner = shape.inset();
+
+		for y in self.visible_rows(shape)
+		{
+			let (left, right) = match shape.row(y)
+			{
+				Some(outer) => outer,
+				None => continue
+			};
+
+			match inner.row(y)
+			{
+				Some((inner_left, inner_right)) if inner_left <= inner_right =>
+				{
+					// steep parts of the curve need more than one pixel per row to stay connected
+					span(self, y, left, i32::max(left, inner_left - 1));
+					span(self, y, i32::min(right, inner_right + 1), right);
+				},
+				_ => span(self, y, left, right)
+			}
+		}
+	}
+
+	fn visible_rows(&self, shape: &RoundedBox) -> core::ops::RangeInclusive<i32>
+	{
+		i32::max(0, shape.first_row())..=i32::min(self.height as i32 - 1, shape.last_row())
+	}
+
+	// inclusive pixel range, converted to the half open float span draw_line_horizontal takes
+	fn fill_span(&mut self, y: i32, left: i32, right: i32, color: u8)
+	{
+		if y >= 0
+		{
+			self.draw_line_horizontal(y as f32, left as f32, right as f32 + 1.0f32, color);
+		}
+	}
+
+	// only the runs of pixels mask accepts are drawn
+	fn fill_span_masked<F: Fn(i32) -> bool>(&mut self, y: i32, left: i32, right: i32, color: u8, mask: F)
+	{
+		let left = i32::max(left, 0);
+		let right = i32::min(right, self.width as i32 - 1);
+		let mut run_start: Option<i32> = None;
+
+		for x in left..=right
+		{
+			match (run_start, mask(x))
+			{
+				(None, true) => run_start = Some(x),
+				(Some(start), false) =>
+				{
+					self.fill_span(y, start, x - 1, color);
+					run_start = None;
+				},
+				_ => ()
+			}
+		}
+
+		if let Some(start) = run_start
+		{
+			self.fill_span(y, start, right, color);
+		}
+	}
+}
@@ -0,0 +1,384 @@
+use core::iter::once;
+use display_interface::{WriteOnlyDataCommand, DisplayError, DataFormat::U8Iter, DataFormat::U16BEIter};
+use embedded_hal::blocking::delay::DelayUs;
+use embedded_hal::digital::v2::{InputPin, OutputPin};
+use crate::{SCREEN_WIDTH, SCREEN_HEIGHT};
+
+// Driver for the ST7789 display controller. The panel is SCREEN_WIDTH x SCREEN_HEIGHT in its
+// native portrait orientation, which is also the size of the controller's memory, so every
+// orientation covers the whole memory without offsets.
+//
+// Every command goes through a display_interface::WriteOnlyDataCommand, the pixel data of the
+// frames usually doesn't: once start_ram_write opened a window the data can be sent by other
+// means (the DMA, see transfer.rs) on the same bus.
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[repr(u8)]
+pub enum Instruction
+{
+	NOP = 0x00,
+	SWRESET = 0x01,
+	RDDID = 0x04,
+	RDDST = 0x09,
+	SLPIN = 0x10,
+	SLPOUT = 0x11,
+	PTLON = 0x12,
+	NORON = 0x13,
+	INVOFF = 0x20,
+	INVON = 0x21,
+	DISPOFF = 0x28,
+	DISPON = 0x29,
+	CASET = 0x2A,
+	RASET = 0x2B,
+	RAMWR = 0x2C,
+	RAMRD = 0x2E,
+	PTLAR = 0x30,
+	VSCRDEF = 0x33,
+	TEOFF = 0x34,
+	TEON = 0x35,
+	MADCTL = 0x36,
+	VSCSAD = 0x37,
+	COLMOD = 0x3A,
+	STE = 0x44,
+	WRDISBV = 0x51,
+	WRCTRLD = 0x53,
+	VCMOFSET = 0xC5
+}
+
+// MADCTL bits: row order, column order, row/column exchange
+const MADCTL_MY: u8 = 0x80;
+const MADCTL_MX: u8 = 0x40;
+const MADCTL_MV: u8 = 0x20;
+
+// WRCTRLD bits: brightness control and backlight on
+const WRCTRLD_BCTRL: u8 = 0x20;
+const WRCTRLD_BL: u8 = 0x04;
+
+// 16 bit RGB565 for the interface and 65k colors
+const COLMOD_RGB565: u8 = 0b0101_0101;
+
+// clockwise rotation of the picture from the native portrait orientation
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Orientation
+{
+	Portrait,
+	Landscape,
+	PortraitFlipped,
+	LandscapeFlipped
+}
+
+impl Orientation
+{
+	pub fn from_degrees(degrees: u16) -> Option<Orientation>
+	{
+		match degrees
+		{
+			0 => Some(Orientation::Portrait),
+			90 => Some(Orientation::Landscape),
+			180 => Some(Orientation::PortraitFlipped),
+			270 => Some(Orientation::LandscapeFlipped),
+			_ => None
+		}
+	}
+
+	pub fn is_landscape(&self) -> bool
+	{
+		matches!(self, Orientation::Landscape | Orientation::LandscapeFlipped)
+	}
+
+	// MADCTL value, mirroring flips the x axis of the picture as it is seen
+	fn madctl(&self, mirrored: bool) -> u8
+	{
+		let value = match self
+		{
+			Orientation::Portrait => 0u8,
+			Orientation::Landscape => MADCTL_MX | MADCTL_MV,
+			Orientation::PortraitFlipped => MADCTL_MX | MADCTL_MY,
+			Orientation::LandscapeFlipped => MADCTL_MY | MADCTL_MV
+		};
+
+		if !mirrored
+		{
+			value
+		}
+		// with rows and columns exchanged x runs along the rows of the panel
+		else if self.is_landscape()
+		{
+			value ^ MADCTL_MY
+		}
+		else
+		{
+			value ^ MADCTL_MX
+		}
+	}
+}
+
+// When the TE line goes high: at the start of the vertical blanking only, or also at the
+// start of every horizontal blanking.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TearingEffect
+{
+	VerticalBlank,
+	HorizontalAndVerticalBlank
+}
+
+pub struct St7789<DI: WriteOnlyDataCommand>
+{
+	interface: DI,
+	orientation: Orientation,
+	mirrored: bool,
+	// rows that stay in place above the scrolled ones, and how many rows scroll
+	scroll_top: u16,
+	scroll_rows: u16
+}
+
+impl<DI: WriteOnlyDataCommand> St7789<DI>
+{
+	// nothing is sent until init
+	pub fn new(interface: DI) -> St7789<DI>
+	{
+		St7789{
+			interface,
+			orientation: Orientation::Portrait,
+			mirrored: false,
+			scroll_top: 0,
+			scroll_rows: SCREEN_HEIGHT as u16
+		}
+	}
+
+	pub fn release(self) -> DI
+	{
+		self.interface
+	}
+
+	pub fn interface(&mut self) -> &mut DI
+	{
+		&mut self.interface
+	}
+
+	// Resets the display and turns it on in the current orientation, the whole panel scrolls
+	// and the memory keeps whatever it had in it.
+	pub fn init<RP: OutputPin>(&mut self, delay: &mut impl DelayUs<u32>, reset_pin: &mut RP) -> Result<(), DisplayError>
+	{
+		// hard reset, the pin changes need a moment to get registered
+		reset_pin.set_high().map_err(|_| DisplayError::RSError)?;
+		delay.delay_us(10);
+		reset_pin.set_low().map_err(|_| DisplayError::RSError)?;
+		delay.delay_us(10);
+		reset_pin.set_high().map_err(|_| DisplayError::RSError)?;
+		delay.delay_us(10);
+
+		self.write_command(Instruction::SWRESET, &[])?;
+		delay.delay_us(150_000);
+		self.wake(delay)?;
+
+		self.set_scroll_area(0, 0)?;
+		self.set_orientation(self.orientation, self.mirrored)?;
+		self.write_command(Instruction::COLMOD, &[COLMOD_RGB565])?;
+		// the panel is IPS, colors only come out right inverted
+		self.set_inverted(true)?;
+		delay.delay_us(10_000);
+		self.set_normal_mode()?;
+		delay.delay_us(10_000);
+		self.write_command(Instruction::DISPON, &[])?;
+		delay.delay_us(10_000);
+
+		Ok(())
+	}
+
+	pub fn write_command(&mut self, command: Instruction, parameters: &[u8]) -> Result<(), DisplayError>
+	{
+		self.interface.send_commands(U8Iter(&mut once(command as u8)))?;
+
+		if !parameters.is_empty()
+		{
+			self.interface.send_data(U8Iter(&mut parameters.iter().cloned()))?;
+		}
+
+		Ok(())
+	}
+
+	pub fn orientation(&self) -> (Orientation, bool)
+	{
+		(self.orientation, self.mirrored)
+	}
+
+	// width and height of the picture in the current orientation
+	pub fn size(&self) -> (usize, usize)
+	{
+		if self.orientation.is_landscape()
+		{
+			(SCREEN_HEIGHT, SCREEN_WIDTH)
+		}
+		else
+		{
+			(SCREEN_WIDTH, SCREEN_HEIGHT)
+		}
+	}
+
+	// address windows and pixel data sent afterwards are in the new orientation, what's
+	// already in the memory isn't moved
+	pub fn set_orientation(&mut self, orientation: Orientation, mirrored: bool) -> Result<(), DisplayError>
+	{
+		self.orientation = orientation;
+		self.mirrored = mirrored;
+		self.write_command(Instruction::MADCTL, &[orientation.madctl(mirrored)])
+	}
+
+	// corners are inclusive, in the current orientation
+	pub fn set_address_window(&mut self, sx: u16, sy: u16, ex: u16, ey: u16) -> Result<(), DisplayError>
+	{
+		let (width, height) = self.size();
+
+		if sx > ex || sy > ey || ex as usize >= width || ey as usize >= height
+		{
+			return Err(DisplayError::OutOfBoundsError);
+		}
+
+		let [sx_high, sx_low] = sx.to_be_bytes();
+		let [ex_high, ex_low] = ex.to_be_bytes();
+		let [sy_high, sy_low] = sy.to_be_bytes();
+		let [ey_high, ey_low] = ey.to_be_bytes();
+
+		self.write_command(Instruction::CASET, &[sx_high, sx_low, ex_high, ex_low])?;
+		self.write_command(Instruction::RASET, &[sy_high, sy_low, ey_high, ey_low])
+	}
+
+	// Opens a window for pixel data and switches the interface to data, the RGB565 pixels can
+	// follow straight on the bus, row by row.
+	pub fn start_ram_write(&mut self, sx: u16, sy: u16, ex: u16, ey: u16) -> Result<(), DisplayError>
+	{
+		self.set_address_window(sx, sy, ex, ey)?;
+		self.write_command(Instruction::RAMWR, &[])?;
+		self.interface.send_data(U8Iter(&mut core::iter::empty()))
+	}
+
+	pub fn write_pixels<T: IntoIterator<Item = u16>>(&mut self, sx: u16, sy: u16, ex: u16, ey: u16, colors: T) -> Result<(), DisplayError>
+	{
+		self.start_ram_write(sx, sy, ex, ey)?;
+		self.interface.send_data(U16BEIter(&mut colors.into_iter()))
+	}
+
+	// Hardware scrolling moves the rows of the panel memory between a fixed top and bottom area,
+	// in portrait that's up and down, in landscape left and right. The scroll offset goes
+	// back to 0.
+	pub fn set_scroll_area(&mut self, top_fixed: u16, bottom_fixed: u16) -> Result<(), DisplayError>
+	{
+		let fixed = top_fixed as usize + bottom_fixed as usize;
+
+		if fixed >= SCREEN_HEIGHT
+		{
+			return Err(DisplayError::OutOfBoundsError);
+		}
+
+		let scroll_rows = (SCREEN_HEIGHT - fixed) as u16;
+		let [top_high, top_low] = top_fixed.to_be_bytes();
+		let [rows_high, rows_low] = scroll_rows.to_be_bytes();
+		let [bottom_high, bottom_low] = bottom_fixed.to_be_bytes();
+
+		self.write_command(Instruction::VSCRDEF, &[top_high, top_low, rows_high, rows_low, bottom_high, bottom_low])?;
+		self.scroll_top = top_fixed;
+		self.scroll_rows = scroll_rows;
+		self.set_scroll_offset(0)
+	}
+
+	// memory row shown at the top of the scroll area, offset rows into it, wrapping around
+	pub fn set_scroll_offset(&mut self, offset: u16) -> Result<(), DisplayError>
+	{
+		let start = self.scroll_top + offset % self.scroll_rows;
+		let [start_high, start_low] = start.to_be_bytes();
+
+		self.write_command(Instruction::VSCSAD, &[start_high, start_low])
+	}
+
+	// Only the panel rows first_row..=last_row are shown, the rest is black. Like scrolling
+	// this works on the rows of the panel, last_row < first_row wraps around the bottom.
+	pub fn set_partial_area(&mut self, first_row: u16, last_row: u16) -> Result<(), DisplayError>
+	{
+		if first_row as usize >= SCREEN_HEIGHT || last_row as usize >= SCREEN_HEIGHT
+		{
+			return Err(DisplayError::OutOfBoundsError);
+		}
+
+		let [first_high, first_low] = first_row.to_be_bytes();
+		let [last_high, last_low] = last_row.to_be_bytes();
+
+		self.write_command(Instruction::PTLAR, &[first_high, first_low, last_high, last_low])?;
+		self.write_command(Instruction::PTLON, &[])
+	}
+
+	// leaves partial mode
+	pub fn set_normal_mode(&mut self) -> Result<(), DisplayError>
+	{
+		self.write_command(Instruction::NORON, &[])
+	}
+
+	// the display needs 5ms before it takes the next command, and 120ms before it can wake
+	pub fn sleep(&mut self, delay: &mut impl DelayUs<u32>) -> Result<(), DisplayError>
+	{
+		self.write_command(Instruction::SLPIN, &[])?;
+		delay.delay_us(5_000);
+		Ok(())
+	}
+
+	// the datasheet asks for 120ms before going back to sleep, that is waited here as well
+	pub fn wake(&mut self, delay: &mut impl DelayUs<u32>) -> Result<(), DisplayError>
+	{
+		self.write_command(Instruction::SLPOUT, &[])?;
+		delay.delay_us(120_000);
+		Ok(())
+	}
+
+	pub fn set_inverted(&mut self, inverted: bool) -> Result<(), DisplayError>
+	{
+		self.write_command(if inverted { Instruction::INVON } else { Instruction::INVOFF }, &[])
+	}
+
+	// Brightness through the controller's backlight control, 0 is off. This only does anything
+	// when the backlight is driven by the controller and not by a pin of its own.
+	pub fn set_brightness(&mut self, brightness: u8) -> Result<(), DisplayError>
+	{
+		self.write_command(Instruction::WRCTRLD, &[WRCTRLD_BCTRL | WRCTRLD_BL])?;
+		self.write_command(Instruction::WRDISBV, &[brightness])
+	}
+
+	// TE line output, None turns it off
+	pub fn set_tearing_effect(&mut self, mode: Option<TearingEffect>) -> Result<(), DisplayError>
+	{
+		match mode
+		{
+			None => self.write_command(Instruction::TEOFF, &[]),
+			Some(TearingEffect::VerticalBlank) => self.write_command(Instruction::TEON, &[0u8]),
+			Some(TearingEffect::HorizontalAndVerticalBlank) => self.write_command(Instruction::TEON, &[1u8])
+		}
+	}
+
+	// with the TE line on, it goes high once the panel has refreshed this row
+	pub fn set_tear_scanline(&mut self, row: u16) -> Result<(), DisplayError>
+	{
+		if row as usize >= SCREEN_HEIGHT
+		{
+			return Err(DisplayError::OutOfBoundsError);
+		}
+
+		let [row_high, row_low] = row.to_be_bytes();
+		self.write_command(Instruction::STE, &[row_high, row_low])
+	}
+}
+
+// Waits for the TE line to go high, the panel has just finished a refresh. Writing a frame
+// from there on stays ahead of the next refresh as long as it's sent faster than the panel
+// refreshes, so the picture doesn't tear.
+pub fn wait_for_tearing_effect<TE: InputPin>(te_pin: &TE) -> Result<(), TE::Error>
+{
+	while te_pin.is_high()?
+	{
+	}
+
+	while te_pin.is_low()?
+	{
+	}
+
+	Ok(())
+}
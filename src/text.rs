@@ -1,207 +1,538 @@
-use crate::{vector::vec2f, framebuffer::{draw_line, draw_pixel}};
-use crate::pixel_ops::colors;
+use crate::{vector::{vec2f, vec3f, vec4f}, framebuffer::Surface, line::LineStyle, font::Font};
+use crate::{matrix::{mat3x3, mat4x4}, viewport::Viewport, clip};
 
-// Letters are represented as lines, pairs of vec2fs
+// Stroke font covering printable ASCII (32 - 126).
+// Every glyph is a list of polylines on an 8 x 10 grid, one byte per point with x in the high
+// nibble and y in the low one. y grows upwards: 0 is the bottom of the descenders, 2 the baseline,
+// 7 the x-height and 10 the cap height. PEN_UP starts a new polyline, GLYPH_END closes the glyph.
 
-#[derive(Clone, Copy)]
-pub struct Letter
+const PEN_UP: u8 = 0xFF;
+const GLYPH_END: u8 = 0xFE;
+
+const FIRST_CHAR: u8 = b' ';
+const GLYPH_COUNT: usize = 95;
+
+const GRID_WIDTH: f32 = 8.0f32;
+const GRID_BASELINE: f32 = 2.0f32;
+const GRID_CAP_HEIGHT: f32 = 10.0f32;
+
+const fn p(x: u8, y: u8) -> u8
+{
+	(x << 4) | y
+}
+
+const UP: u8 = PEN_UP;
+const END: u8 = GLYPH_END;
+
+const GLYPH_DATA: &[u8] = &[
+	// ' '
+	END,
+	// !
+	p(4,10), p(4,4), UP, p(4,3), p(4,2), END,
+	// "
+	p(3,10), p(3,8), UP, p(5,10), p(5,8), END,
+	// #
+	p(3,10), p(2,2), UP, p(6,10), p(5,2), UP, p(1,7), p(7,7), UP, p(1,4), p(7,4), END,
+	// $
+	p(7,9), p(2,9), p(1,8), p(1,7), p(2,6), p(6,6), p(7,5), p(7,4), p(6,3), p(1,3), UP, p(4,10), p(4,2), END,
+	// %
+	p(7,10), p(1,2), UP, p(2,10), p(1,9), p(2,8), p(3,9), p(2,10), UP, p(6,4), p(5,3), p(6,2), p(7,3), p(6,4), END,
+	// &
+	p(7,2), p(2,8), p(3,10), p(5,10), p(6,8), p(1,5), p(1,3), p(2,2), p(5,2), p(7,5), END,
+	// '
+	p(4,10), p(4,8), END,
+	// (
+	p(5,10), p(3,8), p(3,4), p(5,2), END,
+	// )
+	p(3,10), p(5,8), p(5,4), p(3,2), END,
+	// *
+	p(4,9), p(4,3), UP, p(1,8), p(7,4), UP, p(1,4), p(7,8), END,
+	// +
+	p(4,9), p(4,3), UP, p(1,6), p(7,6), END,
+	// ,
+	p(4,3), p(4,2), p(3,0), END,
+	// -
+	p(2,6), p(6,6), END,
+	// .
+	p(4,3), p(4,2), END,
+	// /
+	p(7,10), p(1,2), END,
+	// 0
+	p(2,2), p(1,3), p(1,9), p(2,10), p(6,10), p(7,9), p(7,3), p(6,2), p(2,2), UP, p(7,9), p(1,3), END,
+	// 1
+	p(2,8), p(4,10), p(4,2), UP, p(2,2), p(6,2), END,
+	// 2
+	p(1,9), p(2,10), p(6,10), p(7,9), p(7,7), p(1,2), p(7,2), END,
+	// 3
+	p(1,10), p(7,10), p(4,6), p(6,6), p(7,5), p(7,3), p(6,2), p(2,2), p(1,3), END,
+	// 4
+	p(6,2), p(6,10), p(1,4), p(7,4), END,
+	// 5
+	p(7,10), p(1,10), p(1,7), p(6,7), p(7,6), p(7,3), p(6,2), p(1,2), END,
+	// 6
+	p(6,10), p(3,10), p(1,8), p(1,3), p(2,2), p(6,2), p(7,3), p(7,5), p(6,6), p(1,6), END,
+	// 7
+	p(1,10), p(7,10), p(3,2), END,
+	// 8
+	p(2,6), p(1,7), p(1,9), p(2,10), p(6,10), p(7,9), p(7,7), p(6,6), p(2,6), p(1,5), p(1,3), p(2,2), p(6,2), p(7,3), p(7,5), p(6,6), END,
+	// 9
+	p(7,6), p(2,6), p(1,7), p(1,9), p(2,10), p(6,10), p(7,9), p(7,4), p(5,2), p(2,2), END,
+	// :
+	p(4,7), p(4,6), UP, p(4,3), p(4,2), END,
+	// ;
+	p(4,7), p(4,6), UP, p(4,3), p(4,2), p(3,0), END,
+	// <
+	p(7,9), p(1,6), p(7,3), END,
+	// =
+	p(1,7), p(7,7), UP, p(1,5), p(7,5), END,
+	// >
+	p(1,9), p(7,6), p(1,3), END,
+	// ?
+	p(1,9), p(2,10), p(6,10), p(7,9), p(7,7), p(4,5), p(4,4), UP, p(4,3), p(4,2), END,
+	// @
+	p(6,4), p(6,7), p(3,7), p(3,4), p(6,4), p(7,5), p(7,9), p(6,10), p(2,10), p(1,9), p(1,3), p(2,2), p(7,2), END,
+	// A
+	p(1,2), p(1,8), p(3,10), p(5,10), p(7,8), p(7,2), UP, p(1,6), p(7,6), END,
+	// B
+	p(1,2), p(1,10), p(6,10), p(7,9), p(7,7), p(6,6), p(1,6), UP, p(6,6), p(7,5), p(7,3), p(6,2), p(1,2), END,
+	// C
+	p(7,9), p(6,10), p(2,10), p(1,9), p(1,3), p(2,2), p(6,2), p(7,3), END,
+	// D
+	p(1,2), p(1,10), p(5,10), p(7,8), p(7,4), p(5,2), p(1,2), END,
+	// E
+	p(7,10), p(1,10), p(1,2), p(7,2), UP, p(1,6), p(5,6), END,
+	// F
+	p(7,10), p(1,10), p(1,2), UP, p(1,6), p(5,6), END,
+	// G
+	p(7,9), p(6,10), p(2,10), p(1,9), p(1,3), p(2,2), p(6,2), p(7,3), p(7,6), p(4,6), END,
+	// H
+	p(1,10), p(1,2), UP, p(7,10), p(7,2), UP, p(1,6), p(7,6), END,
+	// I
+	p(2,10), p(6,10), UP, p(4,10), p(4,2), UP, p(2,2), p(6,2), END,
+	// J
+	p(7,10), p(7,3), p(6,2), p(2,2), p(1,3), p(1,4), END,
+	// K
+	p(1,10), p(1,2), UP, p(7,10), p(1,4), UP, p(3,6), p(7,2), END,
+	// L
+	p(1,10), p(1,2), p(7,2), END,
+	// M
+	p(1,2), p(1,10), p(4,5), p(7,10), p(7,2), END,
+	// N
+	p(1,2), p(1,10), p(7,2), p(7,10), END,
+	// O
+	p(2,2), p(1,3), p(1,9), p(2,10), p(6,10), p(7,9), p(7,3), p(6,2), p(2,2), END,
+	// P
+	p(1,2), p(1,10), p(6,10), p(7,9), p(7,7), p(6,6), p(1,6), END,
+	// Q
+	p(2,2), p(1,3), p(1,9), p(2,10), p(6,10), p(7,9), p(7,3), p(6,2), p(2,2), UP, p(5,4), p(7,2), END,
+	// R
+	p(1,2), p(1,10), p(6,10), p(7,9), p(7,7), p(6,6), p(1,6), UP, p(4,6), p(7,2), END,
+	// S
+	p(7,9), p(6,10), p(2,10), p(1,9), p(1,7), p(2,6), p(6,6), p(7,5), p(7,3), p(6,2), p(2,2), p(1,3), END,
+	// T
+	p(1,10), p(7,10), UP, p(4,10), p(4,2), END,
+	// U
+	p(1,10), p(1,3), p(2,2), p(6,2), p(7,3), p(7,10), END,
+	// V
+	p(1,10), p(4,2), p(7,10), END,
+	// W
+	p(1,10), p(2,2), p(4,7), p(6,2), p(7,10), END,
+	// X
+	p(1,10), p(7,2), UP, p(7,10), p(1,2), END,
+	// Y
+	p(1,10), p(4,6), p(7,10), UP, p(4,6), p(4,2), END,
+	// Z
+	p(1,10), p(7,10), p(1,2), p(7,2), END,
+	// [
+	p(5,10), p(3,10), p(3,2), p(5,2), END,
+	// \
+	p(1,10), p(7,2), END,
+	// ]
+	p(3,10), p(5,10), p(5,2), p(3,2), END,
+	// ^
+	p(2,7), p(4,10), p(6,7), END,
+	// _
+	p(1,1), p(7,1), END,
+	// `
+	p(3,10), p(5,8), END,
+	// a
+	p(2,7), p(6,7), p(7,6), p(7,2), UP, p(7,5), p(2,5), p(1,4), p(1,3), p(2,2), p(7,2), END,
+	// b
+	p(1,10), p(1,2), p(6,2), p(7,3), p(7,6), p(6,7), p(1,7), END,
+	// c
+	p(7,7), p(2,7), p(1,6), p(1,3), p(2,2), p(7,2), END,
+	// d
+	p(7,10), p(7,2), p(2,2), p(1,3), p(1,6), p(2,7), p(7,7), END,
+	// e
+	p(1,5), p(7,5), p(7,6), p(6,7), p(2,7), p(1,6), p(1,3), p(2,2), p(7,2), END,
+	// f
+	p(7,10), p(5,10), p(4,9), p(4,2), UP, p(2,7), p(6,7), END,
+	// g
+	p(7,7), p(7,1), p(6,0), p(1,0), UP, p(7,7), p(2,7), p(1,6), p(1,4), p(2,3), p(7,3), END,
+	// h
+	p(1,10), p(1,2), UP, p(1,7), p(6,7), p(7,6), p(7,2), END,
+	// i
+	p(4,7), p(4,2), UP, p(4,10), p(4,9), END,
+	// j
+	p(5,7), p(5,1), p(4,0), p(2,0), UP, p(5,10), p(5,9), END,
+	// k
+	p(1,10), p(1,2), UP, p(6,7), p(1,4), UP, p(3,5), p(6,2), END,
+	// l
+	p(3,10), p(4,10), p(4,3), p(5,2), END,
+	// m
+	p(1,2), p(1,7), p(3,7), p(4,6), p(4,2), UP, p(4,6), p(5,7), p(6,7), p(7,6), p(7,2), END,
+	// n
+	p(1,2), p(1,7), UP, p(1,6), p(2,7), p(6,7), p(7,6), p(7,2), END,
+	// o
+	p(2,2), p(1,3), p(1,6), p(2,7), p(6,7), p(7,6), p(7,3), p(6,2), p(2,2), END,
+	// p
+	p(1,0), p(1,7), p(6,7), p(7,6), p(7,3), p(6,2), p(1,2), END,
+	// q
+	p(7,0), p(7,7), p(2,7), p(1,6), p(1,3), p(2,2), p(7,2), END,
+	// r
+	p(1,2), p(1,7), UP, p(1,5), p(3,7), p(6,7), END,
+	// s
+	p(7,7), p(2,7), p(1,6), p(2,5), p(6,4), p(7,3), p(6,2), p(1,2), END,
+	// t
+	p(3,10), p(3,3), p(4,2), p(6,2), UP, p(1,7), p(6,7), END,
+	// u
+	p(1,7), p(1,3), p(2,2), p(6,2), p(7,3), UP, p(7,7), p(7,2), END,
+	// v
+	p(1,7), p(4,2), p(7,7), END,
+	// w
+	p(1,7), p(2,2), p(4,6), p(6,2), p(7,7), END,
+	// x
+	p(1,7), p(7,2), UP, p(7,7), p(1,2), END,
+	// y
+	p(1,7), p(4,3), UP, p(7,7), p(2,0), END,
+	// z
+	p(1,7), p(7,7), p(1,2), p(7,2), END,
+	// {
+	p(5,10), p(4,9), p(4,7), p(3,6), p(4,5), p(4,3), p(5,2), END,
+	// |
+	p(4,10), p(4,0), END,
+	// }
+	p(3,10), p(4,9), p(4,7), p(5,6), p(4,5), p(4,3), p(3,2), END,
+	// ~
+	p(1,5), p(2,6), p(3,6), p(5,5), p(6,5), p(7,6), END,
+];
+
+// where every glyph starts in GLYPH_DATA, the last entry is the end of the table
+static GLYPH_OFFSETS: [u16; GLYPH_COUNT + 1] = glyph_offsets(GLYPH_DATA);
+
+const fn glyph_offsets(data: &[u8]) -> [u16; GLYPH_COUNT + 1]
 {
-	pub points: [vec2f; 10],
-	pub line_count: usize
+	let mut offsets = [0u16; GLYPH_COUNT + 1];
+	let mut glyph = 0usize;
+	let mut i = 0usize;
+
+	while i < data.len()
+	{
+		if data[i] == GLYPH_END
+		{
+			glyph += 1;
+			offsets[glyph] = (i + 1) as u16;
+		}
+
+		i += 1;
+	}
+
+	// fails the build if a glyph went missing or got added
+	assert!(glyph == GLYPH_COUNT);
+
+	offsets
 }
 
-impl Letter
+// leftmost and rightmost grid columns every glyph reaches, for proportional spacing
+static GLYPH_EXTENTS: [(u8, u8); GLYPH_COUNT] = glyph_extents(GLYPH_DATA);
+
+const fn glyph_extents(data: &[u8]) -> [(u8, u8); GLYPH_COUNT]
 {
-	pub fn scale(&self, factor: f32) -> Letter
+	let mut extents = [(0x0Fu8, 0u8); GLYPH_COUNT];
+	let mut glyph = 0usize;
+	let mut i = 0usize;
+
+	while i < data.len()
 	{
-		let mut out_letter = self.clone();
+		let point = data[i];
+
+		if point == GLYPH_END
+		{
+			// glyphs without strokes are empty at 0
+			if extents[glyph].0 > extents[glyph].1
+			{
+				extents[glyph] = (0u8, 0u8);
+			}
 
-		for i in 0..10
+			glyph += 1;
+		}
+		else if point != PEN_UP
 		{
-			out_letter.points[i] = out_letter.points[i].scale(factor);
+			let x = point >> 4;
+
+			if x < extents[glyph].0
+			{
+				extents[glyph].0 = x;
+			}
+
+			if x > extents[glyph].1
+			{
+				extents[glyph].1 = x;
+			}
 		}
 
-		out_letter
+		i += 1;
+	}
+
+	extents
+}
+
+// the horizontal range a glyph's strokes cover, in the same units as glyph_strokes
+pub fn glyph_extent(char: u8) -> (f32, f32)
+{
+	if (FIRST_CHAR..FIRST_CHAR + GLYPH_COUNT as u8).contains(&char)
+	{
+		let (left, right) = GLYPH_EXTENTS[(char - FIRST_CHAR) as usize];
+		(left as f32 / GRID_WIDTH, right as f32 / GRID_WIDTH)
 	}
+	else
+	{
+		(0.0f32, 0.0f32)
+	}
+}
 
-	pub fn translate(&self, trans_vec: vec2f) -> Letter
+// the line segments of a glyph in font units: x goes from 0 to 1 across the glyph,
+// y is 0 on the baseline and 1 at the cap height, pointing up. Characters outside of
+// printable ASCII have no strokes.
+pub fn glyph_strokes(char: u8) -> GlyphStrokes
+{
+	let points = if (FIRST_CHAR..FIRST_CHAR + GLYPH_COUNT as u8).contains(&char)
 	{
-		let mut out_letter = self.clone();
+		let glyph = (char - FIRST_CHAR) as usize;
+		// the end marker isn't part of the points
+		&GLYPH_DATA[GLYPH_OFFSETS[glyph] as usize..GLYPH_OFFSETS[glyph + 1] as usize - 1]
+	}
+	else
+	{
+		&[]
+	};
+
+	GlyphStrokes{
+		points,
+		index: 0
+	}
+}
+
+pub struct GlyphStrokes
+{
+	points: &'static [u8],
+	index: usize
+}
+
+impl Iterator for GlyphStrokes
+{
+	type Item = (vec2f, vec2f);
 
-		for i in 0..10
+	fn next(&mut self) -> Option<(vec2f, vec2f)>
+	{
+		while self.index + 1 < self.points.len()
 		{
-			out_letter.points[i] = out_letter.points[i].translate(trans_vec);
+			let begin = self.points[self.index];
+			let end = self.points[self.index + 1];
+
+			if begin == PEN_UP || end == PEN_UP
+			{
+				self.index += 1;
+				continue;
+			}
+
+			self.index += 1;
+			return Some((unpack_point(begin), unpack_point(end)));
 		}
 
-		out_letter
+		None
+	}
+}
+
+fn unpack_point(point: u8) -> vec2f
+{
+	vec2f{
+		x: (point >> 4) as f32 / GRID_WIDTH,
+		y: ((point & 0x0F) as f32 - GRID_BASELINE) / (GRID_CAP_HEIGHT - GRID_BASELINE)
 	}
+}
+
+pub fn print_text(framebuffer: &mut Surface, str_in: &[u8], trans_vec: vec2f, scale: f32, color: u8)
+{
+	print_text_styled(framebuffer, str_in, trans_vec, scale, &LineStyle::solid(), color);
+}
 
-	pub fn flip_x(&self) -> Letter
+// trans_vec is the left end of the baseline, every character advances by scale
+// every stroke of every letter is drawn with style
+pub fn print_text_styled(framebuffer: &mut Surface, str_in: &[u8], trans_vec: vec2f, scale: f32, style: &LineStyle, color: u8)
+{
+	for (counter, char) in str_in.iter().enumerate()
 	{
-		let mut out_letter = self.clone();
+		let origin = &trans_vec + &vec2f{x: counter as f32 * scale, y: 0.0f32};
+		draw_glyph(framebuffer, *char, origin, scale * 0.8f32, style, color);
+	}
+}
+
+// the text is laid out the way print_text_styled would at (0, 0) and then moved by transform,
+// so rotations and shears turn around the left end of the baseline
+pub fn print_text_transformed(framebuffer: &mut Surface, str_in: &[u8], transform: &mat3x3, scale: f32, style: &LineStyle, color: u8)
+{
+	text_strokes(str_in, scale, |begin, end| {
+		// font units point up, the screen points down
+		let begin = *transform * &vec2f{x: begin.x, y: -begin.y};
+		let end = *transform * &vec2f{x: end.x, y: -end.y};
+
+		framebuffer.draw_line_styled(&begin, &end, style, color);
+	});
+}
 
-		for i in 0..10
+// text as part of a model: the baseline runs along +x from the model's origin and the glyphs
+// point up along +y, readable from +z. Every character advances by scale model units.
+// The strokes are clipped like wireframe edges and don't touch the z buffer.
+pub fn print_text_3d(framebuffer: &mut Surface, str_in: &[u8], mvp_matrix: &mat4x4, viewport: &Viewport, scale: f32, style: &LineStyle, color: u8)
+{
+	text_strokes(str_in, scale, |begin, end| {
+		let begin = *mvp_matrix * &vec4f{x: begin.x, y: begin.y, z: 0.0f32, w: 1.0f32};
+		let end = *mvp_matrix * &vec4f{x: end.x, y: end.y, z: 0.0f32, w: 1.0f32};
+
+		if let Some((begin, end)) = clip::clip_line(&begin, &end)
 		{
-			out_letter.points[i].x = out_letter.points[i].x * -1.0f32;
+			framebuffer.draw_line_styled(&viewport.to_screen(&begin.divide()).xy(), &viewport.to_screen(&end.divide()).xy(), style, color);
 		}
+	});
+}
 
-		out_letter
+// flat text that always faces the camera, hanging off a point in model space: the baseline
+// goes through the projected anchor and is centered on it. scale is in pixels, like print_text.
+// Nothing is drawn when the anchor is outside of the near and far planes.
+pub fn print_text_billboard(framebuffer: &mut Surface, str_in: &[u8], anchor: &vec3f, mvp_matrix: &mat4x4, viewport: &Viewport, scale: f32, style: &LineStyle, color: u8)
+{
+	let clip_position = *mvp_matrix * &vec4f::from_vec3f(anchor, 1.0f32);
+
+	if clip_position.w <= 0.0f32 || clip_position.z < 0.0f32 || clip_position.z > clip_position.w
+	{
+		return;
 	}
 
-	pub fn flip_y(&self) -> Letter
+	let screen_position = viewport.to_screen(&clip_position.divide()).xy();
+	let width = str_in.len() as f32 * scale;
+	let transform = mat3x3::new_translation(vec2f{x: screen_position.x - width * 0.5f32, y: screen_position.y});
+
+	print_text_transformed(framebuffer, str_in, &transform, scale, style, color);
+}
+
+// every stroke of a line of text with the baseline starting at (0, 0), y pointing up
+fn text_strokes<F: FnMut(vec2f, vec2f)>(str_in: &[u8], scale: f32, mut stroke: F)
+{
+	let glyph_scale = scale * 0.8f32;
+
+	for (counter, char) in str_in.iter().enumerate()
 	{
-		let mut out_letter = self.clone();
+		let origin = vec2f{x: counter as f32 * scale, y: 0.0f32};
 
-		for i in 0..10
+		for (begin, end) in glyph_strokes(*char)
 		{
-			out_letter.points[i].y = out_letter.points[i].y * -1.0f32;
+			stroke(begin.scale(glyph_scale).translate(origin), end.scale(glyph_scale).translate(origin));
 		}
+	}
+}
+
+// origin is the left end of the baseline, glyph_scale the cap height in pixels
+fn draw_glyph(framebuffer: &mut Surface, char: u8, origin: vec2f, glyph_scale: f32, style: &LineStyle, color: u8)
+{
+	for (begin, end) in glyph_strokes(char)
+	{
+		// font units point up, the screen points down
+		let begin = vec2f{x: begin.x, y: -begin.y}.scale(glyph_scale).translate(origin);
+		let end = vec2f{x: end.x, y: -end.y}.scale(glyph_scale).translate(origin);
 
-		out_letter
+		framebuffer.draw_line_styled(&begin, &end, style, color);
 	}
 }
 
-pub fn print_text(framebuffer: &mut [u8], str_in: &[u8], trans_vec: vec2f, scale: f32, color: u8)
+// the stroke font as a Font, laid out the same way print_text does it
+#[derive(Clone, Copy)]
+pub struct StrokeFont
 {
-	let mut counter: f32 = 0.0f32;
-	for char in str_in
+	// horizontal advance in pixels, the cap height is 0.8 of it
+	pub size: f32,
+	pub style: LineStyle,
+	// advance every glyph by its own width instead of size
+	pub proportional: bool
+}
+
+impl StrokeFont
+{
+	pub fn new(size: f32) -> StrokeFont
 	{
-		let current_letter = get_letter_for_char(char).flip_y().scale(scale * 0.8).translate(&trans_vec + &vec2f { x: counter * scale, y: 0.0f32 });
+		StrokeFont{
+			size,
+			style: LineStyle::solid(),
+			proportional: false
+		}
+	}
+
+	pub fn with_style(&self, style: LineStyle) -> StrokeFont
+	{
+		StrokeFont{
+			style,
+			..*self
+		}
+	}
+
+	pub fn with_proportional(&self, proportional: bool) -> StrokeFont
+	{
+		StrokeFont{
+			proportional,
+			..*self
+		}
+	}
+}
 
-		for i in 0..current_letter.line_count
+impl Font for StrokeFont
+{
+	fn advance(&self, char: u8) -> f32
+	{
+		if !self.proportional
 		{
-			draw_line(framebuffer, &current_letter.points[i * 2 + 0], &current_letter.points[i * 2 + 1], color);
+			return self.size;
 		}
 
-		counter += 1.0f32;
-	}
-}
-
-fn get_letter_for_char(char: &u8) -> Letter
-{
-	match char{
-		0u8..=47u8 => Letter{ points: [vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.0f32},
-					vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.0f32},], line_count: 0},
-		58..=64 => Letter{ points: [vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.0f32},
-					vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.0f32},], line_count: 0},
-		91..=127 => Letter{ points: [vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.0f32},
-					vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.0f32},], line_count: 0},
-		// 0
-		48 => Letter{ points: [vec2f{x: 0.0f32, y: 0.5f32},vec2f{x: 0.5f32, y: 1.0f32},		vec2f{x: 0.5f32, y: 1.0f32},vec2f{x: 1.0f32, y: 0.5f32},		vec2f{x: 1.0f32, y: 0.5f32},
-					vec2f{x: 0.5f32, y: 0.0f32},		vec2f{x: 0.5f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.5f32},		vec2f{x: 1.0f32, y: 0.25f32},vec2f{x: 0.0f32, y: 0.0f32},], line_count: 4},
-		// 1
-		49 => Letter{ points: [vec2f{x: 0.5f32, y: 0.0f32},vec2f{x: 0.5f32, y: 1.0f32},		vec2f{x: 0.0f32, y: 0.5f32},vec2f{x: 1.0f32, y: 0.5f32},		vec2f{x: 1.0f32, y: 1.0f32},
-					vec2f{x: 1.0f32, y: 0.0f32},		vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 1.0f32, y: 0.0f32},		vec2f{x: 1.0f32, y: 0.25f32},vec2f{x: 0.0f32, y: 0.0f32},], line_count: 1},
-		// 2
-		50 => Letter{ points: [vec2f{x: 0.0f32, y: 1.0f32},vec2f{x: 1.0f32, y: 0.75f32},	vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 1.0f32, y: 0.75f32},		vec2f{x: 0.0f32, y: 0.0f32},
-					vec2f{x: 1.0f32, y: 0.0f32},		vec2f{x: 1.0f32, y: 0.0f32},vec2f{x: 1.0f32, y: 1.0f32},		vec2f{x: 1.0f32, y: 0.25f32},vec2f{x: 1.0f32, y: 1.0f32},], line_count: 3},
-		// 3
-		51 => Letter{ points: [vec2f{x: 0.0f32, y: 1.0f32},vec2f{x: 1.0f32, y: 0.75f32},	vec2f{x: 1.0f32, y: 0.75f32},vec2f{x: 0.0f32, y: 0.5f32},		vec2f{x: 0.0f32, y: 0.5f32},
-					vec2f{x: 1.0f32, y: 0.25f32},		vec2f{x: 1.0f32, y: 0.25f32},vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 1.0f32}], line_count: 4},
-		// 4
-		52 => Letter{ points: [vec2f{x: 0.25f32, y: 1.0f32},vec2f{x: 0.0f32, y: 0.5f32},	vec2f{x: 0.0f32, y: 0.5f32},vec2f{x: 1.0f32, y: 0.5f32},		vec2f{x: 1.0f32, y: 1.0f32},
-					vec2f{x: 1.0f32, y: 0.0f32},		vec2f{x: 1.0f32, y: 0.25f32},vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 1.0f32}], line_count: 3},
-		// 5
-		53 => Letter{ points: [vec2f{x: 1.0f32, y: 1.0f32},vec2f{x: 0.0f32, y: 1.0f32},		vec2f{x: 0.0f32, y: 1.0f32},vec2f{x: 1.0f32, y: 0.5f32},		vec2f{x: 1.0f32, y: 0.5f32},
-					vec2f{x: 0.0f32, y: 0.0f32},		vec2f{x: 1.0f32, y: 0.25f32},vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 1.0f32}], line_count: 3},
-		// 6
-		54 => Letter{ points: [vec2f{x: 1.0f32, y: 1.0f32},vec2f{x: 0.0f32, y: 0.0f32},		vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 1.0f32, y: 0.0f32},		vec2f{x: 1.0f32, y: 0.0f32},
-					vec2f{x: 0.5f32, y: 0.5f32},		vec2f{x: 1.0f32, y: 0.25f32},vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 1.0f32}], line_count: 3},
-		// 7
-		54 => Letter{ points: [vec2f{x: 1.0f32, y: 1.0f32},vec2f{x: 0.0f32, y: 1.0f32},		vec2f{x: 1.0f32, y: 1.0f32},vec2f{x: 0.0f32, y: 0.0f32},		vec2f{x: 1.0f32, y: 0.0f32},
-					vec2f{x: 0.5f32, y: 0.5f32},		vec2f{x: 1.0f32, y: 0.25f32},vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 1.0f32}], line_count: 2},
-		// 8
-		55 => Letter{ points: [vec2f{x: 0.0f32, y: 1.0f32},vec2f{x: 1.0f32, y: 0.0f32},		vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 1.0f32, y: 1.0f32},		vec2f{x: 0.0f32, y: 1.0f32},
-					vec2f{x: 1.0f32, y: 1.0f32},		vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 1.0f32, y: 0.0f32},		vec2f{x: 1.0f32, y: 0.25f32},vec2f{x: 1.0f32, y: 1.0f32},], line_count: 4},
-		// 9
-		56 => Letter{ points: [vec2f{x: 0.0f32, y: 1.0f32},vec2f{x: 1.0f32, y: 1.0f32},		vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 1.0f32, y: 1.0f32},		vec2f{x: 0.0f32, y: 1.0f32},
-					vec2f{x: 0.5f32, y: 0.5f32},		vec2f{x: 1.0f32, y: 1.0f32},vec2f{x: 1.0f32, y: 1.0f32},		vec2f{x: 1.0f32, y: 0.25f32},vec2f{x: 1.0f32, y: 1.0f32},], line_count: 3},
-
-
-
-		// A
-		65 => Letter{ points: [vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 1.0f32, y: 1.0f32},		vec2f{x: 1.0f32, y: 1.0f32},vec2f{x: 1.0f32, y: 0.0f32},		vec2f{x: 0.0f32, y: 0.5f32},
-					vec2f{x: 1.0f32, y: 0.5f32},		vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.0f32},		vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.0f32},], line_count: 3},
-		// B
-		66 => Letter{ points: [vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 1.0f32},		vec2f{x: 0.0f32, y: 1.0f32},vec2f{x: 1.0f32, y: 0.75f32},		vec2f{x: 1.0f32, y: 0.75f32},
-					vec2f{x: 0.0f32, y: 0.5f32},		vec2f{x: 0.0f32, y: 0.5f32},vec2f{x: 1.0f32, y: 0.25f32},		vec2f{x: 1.0f32, y: 0.25f32},vec2f{x: 0.0f32, y: 0.0f32},], line_count: 5},
-		// C
-		67 => Letter{ points: [vec2f{x: 0.0f32, y: 0.5f32},vec2f{x: 1.0f32, y: 1.0f32},		vec2f{x: 0.0f32, y: 0.5f32},vec2f{x: 1.0f32, y: 0.0f32},		vec2f{x: 1.0f32, y: 0.75f32},
-					vec2f{x: 0.0f32, y: 0.5f32},		vec2f{x: 0.0f32, y: 0.5f32},vec2f{x: 1.0f32, y: 0.25f32},		vec2f{x: 1.0f32, y: 0.25f32},vec2f{x: 0.0f32, y: 0.0f32},], line_count: 2},
-		// D
-		68 => Letter{ points: [vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 1.0f32},		vec2f{x: 0.0f32, y: 1.0f32},vec2f{x: 1.0f32, y: 0.5f32},		vec2f{x: 1.0f32, y: 0.5f32},
-					vec2f{x: 0.0f32, y: 0.0f32},		vec2f{x: 0.0f32, y: 0.5f32},vec2f{x: 1.0f32, y: 0.25f32},		vec2f{x: 1.0f32, y: 0.25f32},vec2f{x: 0.0f32, y: 0.0f32},], line_count: 3},
-		// E
-		69 => Letter{ points: [vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 1.0f32},		vec2f{x: 0.0f32, y: 1.0f32},vec2f{x: 1.0f32, y: 1.0f32},		vec2f{x: 0.0f32, y: 0.5f32},
-					vec2f{x: 1.0f32, y: 0.5f32},		vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 1.0f32, y: 0.0f32},		vec2f{x: 1.0f32, y: 0.25f32},vec2f{x: 0.0f32, y: 0.0f32},], line_count: 4},
-		// F
-		70 => Letter{ points: [vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 1.0f32},		vec2f{x: 0.0f32, y: 1.0f32},vec2f{x: 1.0f32, y: 1.0f32},		vec2f{x: 0.0f32, y: 0.5f32},
-					vec2f{x: 1.0f32, y: 0.5f32},		vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 1.0f32, y: 0.0f32},		vec2f{x: 1.0f32, y: 0.25f32},vec2f{x: 0.0f32, y: 0.0f32},], line_count: 3},
-		// G
-		71 => Letter{ points: [vec2f{x: 0.0f32, y: 0.5f32},vec2f{x: 1.0f32, y: 1.0f32},		vec2f{x: 0.0f32, y: 0.5f32},vec2f{x: 1.0f32, y: 0.0f32},		vec2f{x: 1.0f32, y: 0.0f32},
-					vec2f{x: 1.0f32, y: 0.5f32},		vec2f{x: 0.0f32, y: 0.5f32},vec2f{x: 1.0f32, y: 0.25f32},		vec2f{x: 1.0f32, y: 0.25f32},vec2f{x: 0.0f32, y: 0.0f32},], line_count: 3},
-		// H
-		72 => Letter{ points: [vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 1.0f32},		vec2f{x: 0.0f32, y: 0.5f32},vec2f{x: 1.0f32, y: 0.5f32},		vec2f{x: 1.0f32, y: 1.0f32},
-					vec2f{x: 1.0f32, y: 0.0f32},		vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 1.0f32, y: 0.0f32},		vec2f{x: 1.0f32, y: 0.25f32},vec2f{x: 0.0f32, y: 0.0f32},], line_count: 3},
-		// I
-		73 => Letter{ points: [vec2f{x: 0.5f32, y: 0.0f32},vec2f{x: 0.5f32, y: 1.0f32},		vec2f{x: 0.0f32, y: 0.5f32},vec2f{x: 1.0f32, y: 0.5f32},		vec2f{x: 1.0f32, y: 1.0f32},
-					vec2f{x: 1.0f32, y: 0.0f32},		vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 1.0f32, y: 0.0f32},		vec2f{x: 1.0f32, y: 0.25f32},vec2f{x: 0.0f32, y: 0.0f32},], line_count: 1},
-		// J
-		74 => Letter{ points: [vec2f{x: 0.5f32, y: 0.0f32},vec2f{x: 0.5f32, y: 1.0f32},		vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.5f32, y: 0.0f32},		vec2f{x: 1.0f32, y: 1.0f32},
-					vec2f{x: 1.0f32, y: 0.0f32},		vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 1.0f32, y: 0.0f32},		vec2f{x: 1.0f32, y: 0.25f32},vec2f{x: 0.0f32, y: 0.0f32},], line_count: 2},
-		// K
-		75 => Letter{ points: [vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 1.0f32},		vec2f{x: 0.0f32, y: 0.5f32},vec2f{x: 1.0f32, y: 1.0f32},		vec2f{x: 0.0f32, y: 0.5f32},
-					vec2f{x: 1.0f32, y: 0.0f32},		vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 1.0f32, y: 0.0f32},		vec2f{x: 1.0f32, y: 0.25f32},vec2f{x: 0.0f32, y: 0.0f32},], line_count: 3},
-		// L
-		76 => Letter{ points: [vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 1.0f32},		vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 1.0f32, y: 0.0f32},		vec2f{x: 0.0f32, y: 0.5f32},
-					vec2f{x: 1.0f32, y: 0.0f32},		vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 1.0f32, y: 0.0f32},		vec2f{x: 1.0f32, y: 0.25f32},vec2f{x: 0.0f32, y: 0.0f32},], line_count: 2},
-		// M
-		77 => Letter{ points: [vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 1.0f32},		vec2f{x: 0.0f32, y: 1.0f32},vec2f{x: 0.5f32, y: 0.5f32},		vec2f{x: 0.5f32, y: 0.5f32},
-					vec2f{x: 1.0f32, y: 1.0f32},		vec2f{x: 1.0f32, y: 1.0f32},vec2f{x: 1.0f32, y: 0.0f32},		vec2f{x: 1.0f32, y: 0.25f32},vec2f{x: 0.0f32, y: 0.0f32},], line_count: 4},
-		// N
-		78 => Letter{ points: [vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 1.0f32},		vec2f{x: 0.0f32, y: 1.0f32},vec2f{x: 1.0f32, y: 0.0f32},		vec2f{x: 1.0f32, y: 0.0f32},
-					vec2f{x: 1.0f32, y: 1.0f32},		vec2f{x: 1.0f32, y: 1.0f32},vec2f{x: 0.0f32, y: 0.0f32},		vec2f{x: 1.0f32, y: 0.25f32},vec2f{x: 0.0f32, y: 0.0f32},], line_count: 3},
-		// O
-		79 => Letter{ points: [vec2f{x: 0.0f32, y: 0.5f32},vec2f{x: 0.5f32, y: 1.0f32},		vec2f{x: 0.5f32, y: 1.0f32},vec2f{x: 1.0f32, y: 0.5f32},		vec2f{x: 1.0f32, y: 0.5f32},
-					vec2f{x: 0.5f32, y: 0.0f32},		vec2f{x: 0.5f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.5f32},		vec2f{x: 1.0f32, y: 0.25f32},vec2f{x: 0.0f32, y: 0.0f32},], line_count: 4},
-		// P
-		80 => Letter{ points: [vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 1.0f32},		vec2f{x: 0.0f32, y: 1.0f32},vec2f{x: 0.75f32, y: 0.75f32},		vec2f{x: 0.75f32, y: 0.75f32},
-					vec2f{x: 0.0f32, y: 0.5f32},		vec2f{x: 0.5f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.5f32},		vec2f{x: 1.0f32, y: 0.25f32},vec2f{x: 0.0f32, y: 0.0f32},], line_count: 3},
-		// Q
-		81 => Letter{ points: [vec2f{x: 0.0f32, y: 0.5f32},vec2f{x: 0.5f32, y: 1.0f32},		vec2f{x: 0.5f32, y: 1.0f32},vec2f{x: 1.0f32, y: 0.5f32},		vec2f{x: 1.0f32, y: 0.5f32},
-					vec2f{x: 0.5f32, y: 0.0f32},		vec2f{x: 0.5f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.5f32},		vec2f{x: 0.5f32, y: 0.5f32},vec2f{x: 1.0f32, y: 0.0f32},], line_count: 5},
-		// R
-		82 => Letter{ points: [vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 1.0f32},		vec2f{x: 0.0f32, y: 1.0f32},vec2f{x: 0.75f32, y: 0.75f32},		vec2f{x: 0.75f32, y: 0.75f32},
-					vec2f{x: 0.0f32, y: 0.5f32},		vec2f{x: 0.0f32, y: 0.5f32},vec2f{x: 1.0f32, y: 0.0f32},		vec2f{x: 1.0f32, y: 0.25f32},vec2f{x: 0.0f32, y: 0.0f32},], line_count: 4},
-		// S
-		83 => Letter{ points: [vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 1.0f32, y: 0.25f32},	vec2f{x: 1.0f32, y: 0.25f32},vec2f{x: 0.0f32, y: 0.75f32},		vec2f{x: 0.0f32, y: 0.75f32},
-					vec2f{x: 1.0f32, y: 1.0f32},		vec2f{x: 0.5f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.5f32},		vec2f{x: 1.0f32, y: 0.25f32},vec2f{x: 0.0f32, y: 0.0f32},], line_count: 3},
-		// T
-		84 => Letter{ points: [vec2f{x: 0.0f32, y: 1.0f32},vec2f{x: 1.0f32, y: 1.0f32},		vec2f{x: 0.5f32, y: 0.0f32},vec2f{x: 0.5f32, y: 1.0f32},		vec2f{x: 0.0f32, y: 0.75f32},
-					vec2f{x: 1.0f32, y: 1.0f32},		vec2f{x: 0.5f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.5f32},		vec2f{x: 1.0f32, y: 0.25f32},vec2f{x: 0.0f32, y: 0.0f32},], line_count: 2},
-		// U
-		85 => Letter{ points: [vec2f{x: 0.0f32, y: 0.25f32},vec2f{x: 0.0f32, y: 1.0f32},	vec2f{x: 0.0f32, y: 0.25f32},vec2f{x: 0.25f32, y: 0.0f32},		vec2f{x: 0.25f32, y: 0.0f32},
-					vec2f{x: 0.75f32, y: 0.0f32},		vec2f{x: 0.75f32, y: 0.0f32},vec2f{x: 1.0f32, y: 0.25f32},		vec2f{x: 1.0f32, y: 0.25f32},vec2f{x: 1.0f32, y: 1.0f32},], line_count: 5},
-		// V
-		86 => Letter{ points: [vec2f{x: 0.0f32, y: 1.0f32},vec2f{x: 0.5f32, y: 0.0f32},		vec2f{x: 0.5f32, y: 0.0f32},vec2f{x: 1.0f32, y: 1.0f32},		vec2f{x: 0.25f32, y: 0.0f32},
-					vec2f{x: 0.75f32, y: 0.0f32},		vec2f{x: 0.75f32, y: 0.0f32},vec2f{x: 1.0f32, y: 0.25f32},		vec2f{x: 1.0f32, y: 0.25f32},vec2f{x: 1.0f32, y: 1.0f32},], line_count: 2},
-		// W
-		87 => Letter{ points: [vec2f{x: 0.0f32, y: 1.0f32},vec2f{x: 0.0f32, y: 0.0f32},		vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.5f32, y: 0.5f32},		vec2f{x: 0.5f32, y: 0.5f32},
-					vec2f{x: 1.0f32, y: 0.0f32},		vec2f{x: 1.0f32, y: 0.0f32},vec2f{x: 1.0f32, y: 1.0f32},		vec2f{x: 1.0f32, y: 0.25f32},vec2f{x: 1.0f32, y: 1.0f32},], line_count: 4},
-		// X
-		88 => Letter{ points: [vec2f{x: 0.0f32, y: 1.0f32},vec2f{x: 1.0f32, y: 0.0f32},		vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 1.0f32, y: 1.0f32},		vec2f{x: 0.5f32, y: 0.5f32},
-					vec2f{x: 1.0f32, y: 0.0f32},		vec2f{x: 1.0f32, y: 0.0f32},vec2f{x: 1.0f32, y: 1.0f32},		vec2f{x: 1.0f32, y: 0.25f32},vec2f{x: 1.0f32, y: 1.0f32},], line_count: 2},
-		// Y
-		89 => Letter{ points: [vec2f{x: 0.0f32, y: 1.0f32},vec2f{x: 0.5f32, y: 0.5f32},		vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 1.0f32, y: 1.0f32},		vec2f{x: 0.5f32, y: 0.5f32},
-					vec2f{x: 1.0f32, y: 0.0f32},		vec2f{x: 1.0f32, y: 0.0f32},vec2f{x: 1.0f32, y: 1.0f32},		vec2f{x: 1.0f32, y: 0.25f32},vec2f{x: 1.0f32, y: 1.0f32},], line_count: 2},
-		// Z
-		90 => Letter{ points: [vec2f{x: 0.0f32, y: 1.0f32},vec2f{x: 1.0f32, y: 1.0f32},		vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 1.0f32, y: 1.0f32},		vec2f{x: 0.0f32, y: 0.0f32},
-					vec2f{x: 1.0f32, y: 0.0f32},		vec2f{x: 1.0f32, y: 0.0f32},vec2f{x: 1.0f32, y: 1.0f32},		vec2f{x: 1.0f32, y: 0.25f32},vec2f{x: 1.0f32, y: 1.0f32},], line_count: 3},
-		
-			
-			
-							
-		_ => Letter{ points: [vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.0f32},
-		vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.0f32},vec2f{x: 0.0f32, y: 0.0f32},], line_count: 0}
-	}
-}
\ No newline at end of file
+		let glyph_scale = self.size * 0.8f32;
+
+		// a space has no strokes to measure
+		if char == b' '
+		{
+			return glyph_scale * 0.5f32;
+		}
+
+		// two grid columns of gap, the same as the widest glyphs get in fixed spacing
+		let (left, right) = glyph_extent(char);
+		(right - left + 2.0f32 / GRID_WIDTH) * glyph_scale
+	}
+
+	fn ascent(&self) -> f32
+	{
+		self.size * 0.8f32
+	}
+
+	// room for the descenders plus a little gap
+	fn line_height(&self) -> f32
+	{
+		self.size * 1.25f32
+	}
+
+	fn draw_char(&self, framebuffer: &mut Surface, char: u8, position: vec2f, color: u8)
+	{
+		let glyph_scale = self.size * 0.8f32;
+		// proportional glyphs start right at the position, without the blank columns on their left
+		let left = if self.proportional { glyph_extent(char).0 * glyph_scale } else { 0.0f32 };
+		let origin = &position + &vec2f{x: -left, y: self.ascent()};
+		draw_glyph(framebuffer, char, origin, glyph_scale, &self.style, color);
+	}
+}
@@ -0,0 +1,81 @@
+use crate::pixel_ops::colors;
+
+// what happens to texture coordinates outside of the [0, 1] range
+#[derive(Clone, Copy, PartialEq)]
+pub enum AddressMode
+{
+	Wrap,
+	Clamp
+}
+
+// RGB 332 image stored row by row, same layout framebuffer::draw_array expects
+#[derive(Clone, Copy)]
+pub struct Texture
+{
+	pub data: &'static [u8],
+	pub width: usize,
+	pub height: usize,
+	pub address_mode: AddressMode
+}
+
+const W: u8 = colors::WHITE as u8;
+const R: u8 = colors::RED as u8;
+
+// 8x8 fallback texture for when there's no image data around
+static CHECKERBOARD: [u8; 64] = [
+	W, R, W, R, W, R, W, R,
+	R, W, R, W, R, W, R, W,
+	W, R, W, R, W, R, W, R,
+	R, W, R, W, R, W, R, W,
+	W, R, W, R, W, R, W, R,
+	R, W, R, W, R, W, R, W,
+	W, R, W, R, W, R, W, R,
+	R, W, R, W, R, W, R, W
+];
+
+impl Texture
+{
+	pub fn new(data: &'static [u8], width: usize, height: usize) -> Texture
+	{
+		Texture{
+			data,
+			width,
+			height,
+			address_mode: AddressMode::Wrap
+		}
+	}
+
+	pub fn checkerboard() -> Texture
+	{
+		Texture::new(&CHECKERBOARD, 8, 8)
+	}
+
+	pub fn with_address_mode(&self, address_mode: AddressMode) -> Texture
+	{
+		Texture{
+			address_mode,
+			..*self
+		}
+	}
+
+	// nearest neighbour lookup, u goes left -> right and v goes top -> bottom
+	pub fn sample_nearest(&self, u: f32, v: f32) -> u8
+	{
+		let x = self.address(u, self.width);
+		let y = self.address(v, self.height);
+
+		self.data[y * self.width + x]
+	}
+
+	// turn a texture coordinate into a texel index along an axis of the given size
+	fn address(&self, coord: f32, size: usize) -> usize
+	{
+		let coord = match self.address_mode
+		{
+			AddressMode::Wrap => coord - libm::floorf(coord),
+			AddressMode::Clamp => coord.clamp(0.0f32, 1.0f32)
+		};
+
+		usize::min(size - 1, (coord * size as f32) as usize)
+	}
+}
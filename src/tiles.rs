@@ -0,0 +1,187 @@
+use crate::framebuffer::Surface;
+use crate::model::{triangle3d, shaded_triangle, TriangleSink};
+use crate::texture::Texture;
+
+// Tiled rendering: the triangles of a frame are projected once and binned by the horizontal
+// strips of the screen they cover, then every strip is rasterized on its own into a frame and
+// depth buffer only as big as the strip. A strip can be sent to the display while the next
+// one is drawn, so two strip buffers and one strip depth buffer replace full screen ones.
+//
+//   bins.clear(), then mesh.bin_*(&mut bins, ...) for everything in the frame
+//   for every strip: clear the strip buffers, bins.draw_strip(strip, ...), send the strip
+
+#[derive(Clone, Copy)]
+pub enum BinnedTriangle
+{
+	Flat{triangle: triangle3d, color: u8},
+	// the texture is copied, it's only a reference to the texels and their layout
+	Shaded{triangle: shaded_triangle, texture: Option<Texture>, dither: bool}
+}
+
+impl BinnedTriangle
+{
+	// the same triangle with its screen y moved by offset
+	fn moved_down(&self, offset: f32) -> BinnedTriangle
+	{
+		let mut moved = *self;
+
+		match &mut moved
+		{
+			BinnedTriangle::Flat{triangle, ..} =>
+			{
+				for point in triangle.points.iter_mut()
+				{
+					point.y += offset;
+				}
+			},
+			BinnedTriangle::Shaded{triangle, ..} =>
+			{
+				for point in triangle.points.iter_mut()
+				{
+					point.position.y += offset;
+				}
+			}
+		}
+
+		moved
+	}
+}
+
+// Up to CAPACITY triangles with the range of strips each one touches. Triangles that don't fit
+// anymore are dropped and counted, a frame that drops any needs bigger bins.
+pub struct TriangleBins<const CAPACITY: usize>
+{
+	screen_height: usize,
+	strip_height: usize,
+	triangles: [Option<BinnedTriangle>; CAPACITY],
+	// first and last strip of every triangle
+	strips: [(usize, usize); CAPACITY],
+	count: usize,
+	dropped: usize
+}
+
+impl<const CAPACITY: usize> TriangleBins<CAPACITY>
+{
+	pub const fn new(screen_height: usize, strip_height: usize) -> TriangleBins<CAPACITY>
+	{
+		TriangleBins{
+			screen_height,
+			strip_height,
+			triangles: [None; CAPACITY],
+			strips: [(0, 0); CAPACITY],
+			count: 0,
+			dropped: 0
+		}
+	}
+
+	pub fn clear(&mut self)
+	{
+		self.count = 0;
+		self.dropped = 0;
+	}
+
+	pub fn len(&self) -> usize
+	{
+		self.count
+	}
+
+	pub fn is_empty(&self) -> bool
+	{
+		self.count == 0
+	}
+
+	// triangles that didn't fit since the last clear
+	pub fn dropped(&self) -> usize
+	{
+		self.dropped
+	}
+
+	pub fn strip_height(&self) -> usize
+	{
+		self.strip_height
+	}
+
+	pub fn strip_count(&self) -> usize
+	{
+		self.screen_height.div_ceil(self.strip_height)
+	}
+
+	// first screen row and number of rows of a strip, the last one can be shorter
+	pub fn strip_rows(&self, strip: usize) -> (usize, usize)
+	{
+		let first_row = strip * self.strip_height;
+		(first_row, usize::min(self.strip_height, self.screen_height - first_row))
+	}
+
+	// the triangles touching a strip, in the order they were added
+	pub fn strip_triangles(&self, strip: usize) -> impl Iterator<Item = &BinnedTriangle>
+	{
+		self.triangles[..self.count].iter().zip(self.strips[..self.count].iter())
+			.filter(move |(_, (first, last))| *first <= strip && strip <= *last)
+			.filter_map(|(triangle, _)| triangle.as_ref())
+	}
+
+	// Draws the triangles of a strip. framebuffer and depthbuffer cover only the rows of the
+	// strip and are expected to be cleared already.
+	pub fn draw_strip(&self, strip: usize, framebuffer: &mut Surface, depthbuffer: &mut [u16])
+	{
+		let (first_row, _) = self.strip_rows(strip);
+
+		for triangle in self.strip_triangles(strip)
+		{
+			match triangle.moved_down(-(first_row as f32))
+			{
+				BinnedTriangle::Flat{triangle, color} => triangle.draw(framebuffer, depthbuffer, color),
+				BinnedTriangle::Shaded{triangle, texture, dither} => triangle.draw(framebuffer, depthbuffer, texture.as_ref(), dither)
+			}
+		}
+	}
+
+	// top and bottom are the screen y range of the triangle, it's skipped when that's off screen
+	fn add(&mut self, triangle: BinnedTriangle, top: f32, bottom: f32)
+	{
+		if bottom < 0.0f32 || top >= self.screen_height as f32
+		{
+			return;
+		}
+
+		if self.count == CAPACITY
+		{
+			self.dropped += 1;
+			return;
+		}
+
+		// a half pixel of slack on both ends, spans are drawn on the rows their centers fall on
+		let first_row = f32::max(top - 0.5f32, 0.0f32) as usize;
+		let last_row = usize::min(f32::max(bottom + 0.5f32, 0.0f32) as usize, self.screen_height - 1);
+
+		self.triangles[self.count] = Some(triangle);
+		self.strips[self.count] = (first_row / self.strip_height, last_row / self.strip_height);
+		self.count += 1;
+	}
+}
+
+impl<const CAPACITY: usize> TriangleSink for TriangleBins<CAPACITY>
+{
+	fn flat(&mut self, triangle: &triangle3d, color: u8)
+	{
+		let ys = triangle.points.map(|point| point.y);
+		self.add(BinnedTriangle::Flat{triangle: *triangle, color}, min3(ys), max3(ys));
+	}
+
+	fn shaded(&mut self, triangle: &shaded_triangle, texture: Option<&Texture>, dither: bool)
+	{
+		let ys = triangle.points.map(|point| point.position.y);
+		self.add(BinnedTriangle::Shaded{triangle: *triangle, texture: texture.copied(), dither}, min3(ys), max3(ys));
+	}
+}
+
+fn min3(values: [f32; 3]) -> f32
+{
+	f32::min(values[0], f32::min(values[1], values[2]))
+}
+
+fn max3(values: [f32; 3]) -> f32
+{
+	f32::max(values[0], f32::max(values[1], values[2]))
+}
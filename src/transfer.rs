@@ -0,0 +1,265 @@
+use crate::pixel_ops::convert_pixel;
+use crate::damage::{DamageList, DamageRect};
+use crate::{SCREEN_WIDTH, SCREEN_HEIGHT};
+
+// Streams finished RGB 332 frames to the display in the background. Every row is expanded
+// to big endian RGB 565 in one of two line buffers and handed to the DMA; while the DMA sends
+// one line the next one gets expanded into the other buffer. The end of every line has to be
+// reported with line_done, from the DMA interrupt on the RP2040 and by hand in tests.
+// Either the whole frame is sent or only the rectangles of a damage list, one address window each.
+//
+// A frame is lent to the transfer for as long as it's being sent and handed back through
+// take_finished afterwards, so with two framebuffers the next frame can be rendered while
+// the previous one goes out:
+//
+//   render into back
+//   wait for take_finished to return the front buffer
+//   start(back), then render into what used to be the front buffer
+//
+// The tiled renderer sends strips instead of frames with start_strip, the same way.
+
+pub const LINE_BYTES: usize = SCREEN_WIDTH * 2;
+
+// the hardware side of a transfer
+pub trait DisplayDma
+{
+	type Error;
+
+	// get the display ready for the pixel data of a rectangle (inclusive screen coordinates),
+	// called before its first line, an error stops the transfer
+	fn begin_window(&mut self, rect: &DamageRect) -> Result<(), Self::Error>;
+	// start sending data and return right away, data stays untouched until line_done
+	fn start_line(&mut self, data: &[u8]);
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum TransferState
+{
+	// no frame lent to the transfer
+	Idle,
+	// row line of the rect-th rectangle is on its way to the display
+	Sending{rect: usize, line: usize},
+	// every line was sent, the frame is waiting for take_finished
+	Complete,
+	// the display didn't take an address window, the rest of the frame was dropped and it's
+	// waiting for take_finished
+	Failed
+}
+
+pub struct FrameTransfer<D: DisplayDma>
+{
+	pub dma: D,
+	line_buffers: [[u8; LINE_BYTES]; 2],
+	frame: Option<&'static mut [u8]>,
+	// screen row the first row of the frame goes to, only strips start further down
+	first_row: usize,
+	rects: DamageList,
+	// line buffer the next line goes into
+	next_buffer: usize,
+	state: TransferState
+}
+
+impl<D: DisplayDma> FrameTransfer<D>
+{
+	pub fn new(dma: D) -> FrameTransfer<D>
+	{
+		FrameTransfer{
+			dma,
+			line_buffers: [[0u8; LINE_BYTES]; 2],
+			frame: None,
+			first_row: 0,
+			rects: DamageList::new(),
+			next_buffer: 0,
+			state: TransferState::Idle
+		}
+	}
+
+	pub fn state(&self) -> TransferState
+	{
+		self.state
+	}
+
+	// the completion flag, the last line of the current frame has been sent
+	pub fn is_complete(&self) -> bool
+	{
+		self.state == TransferState::Complete
+	}
+
+	// Starts sending a SCREEN_WIDTH x SCREEN_HEIGHT frame. Only one frame can be sent at a time,
+	// while another one is still lent to the transfer the new one is handed back as the error.
+	pub fn start(&mut self, frame: &'static mut [u8]) -> Result<(), &'static mut [u8]>
+	{
+		let mut whole_screen = DamageList::new();
+		whole_screen.add(DamageRect{left: 0, top: 0, right: SCREEN_WIDTH - 1, bottom: SCREEN_HEIGHT - 1});
+
+		self.start_regions(frame, &whole_screen)
+	}
+
+	// same as start, but only the parts of the frame inside the rectangles are sent
+	// an empty list completes right away
+	pub fn start_regions(&mut self, frame: &'static mut [u8], rects: &DamageList) -> Result<(), &'static mut [u8]>
+	{
+		if frame.len() < SCREEN_WIDTH * SCREEN_HEIGHT
+		{
+			return Err(frame);
+		}
+
+		self.begin(frame, 0, rects)
+	}
+
+	// Starts sending a strip of whole rows, the ones from first_row on. The strip has to be a
+	// multiple of SCREEN_WIDTH long and end on the screen.
+	pub fn start_strip(&mut self, strip: &'static mut [u8], first_row: usize) -> Result<(), &'static mut [u8]>
+	{
+		let rows = strip.len() / SCREEN_WIDTH;
+
+		if rows == 0 || rows * SCREEN_WIDTH != strip.len() || first_row + rows > SCREEN_HEIGHT
+		{
+			return Err(strip);
+		}
+
+		let mut window = DamageList::new();
+		window.add(DamageRect{left: 0, top: first_row, right: SCREEN_WIDTH - 1, bottom: first_row + rows - 1});
+
+		self.begin(strip, first_row, &window)
+	}
+
+	fn begin(&mut self, frame: &'static mut [u8], first_row: usize, rects: &DamageList) -> Result<(), &'static mut [u8]>
+	{
+		if self.state != TransferState::Idle
+		{
+			return Err(frame);
+		}
+
+		self.frame = Some(frame);
+		self.first_row = first_row;
+		self.rects.clear();
+
+		// anything off screen is cut off
+		for rect in rects.rects()
+		{
+			if rect.left < SCREEN_WIDTH && rect.top < SCREEN_HEIGHT
+			{
+				self.rects.add(DamageRect{
+					right: usize::min(rect.right, SCREEN_WIDTH - 1),
+					bottom: usize::min(rect.bottom, SCREEN_HEIGHT - 1),
+					..*rect
+				});
+			}
+		}
+
+		match self.rects.rects().first()
+		{
+			Some(first) =>
+			{
+				let (rect, line) = (0, first.top);
+				self.next_buffer = 0;
+				self.prepare_line(rect, line);
+
+				// the error itself only comes out of line_done, the state says what happened
+				let _ = self.send_line(rect, line);
+			},
+			None => self.state = TransferState::Complete
+		}
+
+		Ok(())
+	}
+
+	// The DMA finished the line it was given last. When the display doesn't take the window of
+	// the next rectangle the transfer stops as Failed and the error is returned, the frame
+	// comes back through take_finished either way.
+	pub fn line_done(&mut self) -> Result<(), D::Error>
+	{
+		let (rect, line) = match self.state
+		{
+			TransferState::Sending{rect, line} => match self.next_line(rect, line)
+			{
+				Some(next) => next,
+				None =>
+				{
+					self.state = TransferState::Complete;
+					return Ok(());
+				}
+			},
+			// spurious interrupt
+			_ => return Ok(())
+		};
+
+		self.send_line(rect, line)
+	}
+
+	// hands the frame back once it's been sent or the transfer failed, the transfer is idle again afterwards
+	pub fn take_finished(&mut self) -> Option<&'static mut [u8]>
+	{
+		if self.state != TransferState::Complete && self.state != TransferState::Failed
+		{
+			return None;
+		}
+
+		self.state = TransferState::Idle;
+		self.frame.take()
+	}
+
+	// the line after line of the rect-th rectangle, the first line of the next rectangle after its last one
+	fn next_line(&self, rect: usize, line: usize) -> Option<(usize, usize)>
+	{
+		let rects = self.rects.rects();
+
+		if line < rects[rect].bottom
+		{
+			Some((rect, line + 1))
+		}
+		else
+		{
+			rects.get(rect + 1).map(|next| (rect + 1, next.top))
+		}
+	}
+
+	// hands the prepared line to the DMA and expands the one after it into the other buffer
+	fn send_line(&mut self, rect: usize, line: usize) -> Result<(), D::Error>
+	{
+		let bounds = self.rects.rects()[rect];
+
+		if line == bounds.top
+		{
+			if let Err(error) = self.dma.begin_window(&bounds)
+			{
+				self.state = TransferState::Failed;
+				return Err(error);
+			}
+		}
+
+		self.state = TransferState::Sending{rect, line};
+		let buffer = self.next_buffer;
+		self.next_buffer = 1 - buffer;
+		self.dma.start_line(&self.line_buffers[buffer][..bounds.width() * 2]);
+
+		if let Some((next_rect, next_line)) = self.next_line(rect, line)
+		{
+			self.prepare_line(next_rect, next_line);
+		}
+
+		Ok(())
+	}
+
+	// expand a line into the buffer the DMA isn't reading from
+	fn prepare_line(&mut self, rect: usize, line: usize)
+	{
+		let bounds = self.rects.rects()[rect];
+
+		if let Some(frame) = &self.frame
+		{
+			let row = (line - self.first_row) * SCREEN_WIDTH;
+			expand_line(&frame[row + bounds.left..=row + bounds.right], &mut self.line_buffers[self.next_buffer]);
+		}
+	}
+}
+
+// RGB 332 to RGB 565, high byte first like the display expects it
+pub fn expand_line(pixels: &[u8], out_bytes: &mut [u8])
+{
+	for (pix, out) in pixels.iter().zip(out_bytes.chunks_exact_mut(2))
+	{
+		out.copy_from_slice(&convert_pixel(*pix).to_be_bytes());
+	}
+}
@@ -1,4 +1,4 @@
-use core::ops::{self, Add, Sub, Mul, Div, AddAssign, SubAssign, MulAssign, DivAssign};
+use core::ops;
 
 #[derive(Clone, Copy)]
 pub struct vec2f
@@ -52,6 +52,65 @@ impl vec3f
 			z: self.z + v3.z
 		}
 	}
+
+	pub fn sub(&self, v3: &vec3f) -> vec3f
+	{
+		vec3f{
+			x: self.x - v3.x,
+			y: self.y - v3.y,
+			z: self.z - v3.z
+		}
+	}
+
+	pub fn dot(&self, v3: &vec3f) -> f32
+	{
+		self.x * v3.x + self.y * v3.y + self.z * v3.z
+	}
+
+	pub fn cross(&self, v3: &vec3f) -> vec3f
+	{
+		vec3f{
+			x: self.y * v3.z - self.z * v3.y,
+			y: self.z * v3.x - self.x * v3.z,
+			z: self.x * v3.y - self.y * v3.x
+		}
+	}
+
+	pub fn length(&self) -> f32
+	{
+		libm::sqrtf(self.dot(self))
+	}
+
+	// zero length vectors are returned unchanged
+	pub fn normalize(&self) -> vec3f
+	{
+		let length = self.length();
+
+		if length > 0.0f32
+		{
+			self.scale(1.0f32 / length)
+		}
+		else
+		{
+			*self
+		}
+	}
+
+	// drop the z component, used when a projected point only needs its screen position
+	pub fn xy(&self) -> vec2f
+	{
+		vec2f{
+			x: self.x,
+			y: self.y
+		}
+	}
+}
+
+impl Default for vec3f
+{
+	fn default() -> Self {
+		vec3f::new()
+	}
 }
 
 impl ops::Add<&vec3f> for vec3f 
@@ -79,6 +138,63 @@ impl vec4f
 			w: 0.0f32
 		}
 	}
+
+	pub fn from_vec3f(v3: &vec3f, w: f32) -> vec4f
+	{
+		vec4f{
+			x: v3.x,
+			y: v3.y,
+			z: v3.z,
+			w
+		}
+	}
+
+	pub fn add(&self, v4: &vec4f) -> vec4f
+	{
+		vec4f{
+			x: self.x + v4.x,
+			y: self.y + v4.y,
+			z: self.z + v4.z,
+			w: self.w + v4.w
+		}
+	}
+
+	pub fn sub(&self, v4: &vec4f) -> vec4f
+	{
+		vec4f{
+			x: self.x - v4.x,
+			y: self.y - v4.y,
+			z: self.z - v4.z,
+			w: self.w - v4.w
+		}
+	}
+
+	pub fn scale(&self, factor: f32) -> vec4f
+	{
+		vec4f{
+			x: self.x * factor,
+			y: self.y * factor,
+			z: self.z * factor,
+			w: self.w * factor
+		}
+	}
+
+	// perspective divide, only safe for points that survived near plane clipping
+	pub fn divide(&self) -> vec3f
+	{
+		vec3f{
+			x: self.x / self.w,
+			y: self.y / self.w,
+			z: self.z / self.w
+		}
+	}
+}
+
+impl Default for vec4f
+{
+	fn default() -> Self {
+		vec4f::new()
+	}
 }
 
 impl vec2f
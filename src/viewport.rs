@@ -0,0 +1,62 @@
+use crate::vector::vec3f;
+use crate::{SCREEN_WIDTH, SCREEN_HEIGHT};
+
+// Part of the framebuffer the normalized device coordinates get mapped onto.
+// x and y are the top left corner in pixels, the depth range is where
+// the near and far planes end up in the z buffer (0 -> 1 uses all of it).
+#[derive(Clone, Copy)]
+pub struct Viewport
+{
+	pub x: f32,
+	pub y: f32,
+	pub width: f32,
+	pub height: f32,
+	pub min_depth: f32,
+	pub max_depth: f32
+}
+
+impl Viewport
+{
+	pub fn new(x: f32, y: f32, width: f32, height: f32) -> Viewport
+	{
+		Viewport{
+			x,
+			y,
+			width,
+			height,
+			min_depth: 0.0f32,
+			max_depth: 1.0f32
+		}
+	}
+
+	// the whole display
+	pub fn fullscreen() -> Viewport
+	{
+		Viewport::new(0.0f32, 0.0f32, SCREEN_WIDTH as f32, SCREEN_HEIGHT as f32)
+	}
+
+	pub fn with_depth_range(&self, min_depth: f32, max_depth: f32) -> Viewport
+	{
+		Viewport{
+			min_depth,
+			max_depth,
+			..*self
+		}
+	}
+
+	// width / height, meant for mat4x4::new_projection
+	pub fn aspect_ratio(&self) -> f32
+	{
+		self.width / self.height
+	}
+
+	// normalized device coordinates to pixels, y is flipped since the framebuffer goes top -> bottom
+	pub fn to_screen(&self, ndc: &vec3f) -> vec3f
+	{
+		vec3f{
+			x: self.x + (ndc.x + 1.0f32) * 0.5f32 * self.width,
+			y: self.y + (1.0f32 - ndc.y) * 0.5f32 * self.height,
+			z: self.min_depth + ndc.z * (self.max_depth - self.min_depth)
+		}
+	}
+}